@@ -0,0 +1,346 @@
+use crate::{
+    i2c, ic, nb, Capabilities, ConfigCheck, DeviceVariant, Error, Events, InterruptInfo,
+    InterruptPinLatching, InterruptPinPolarity, Kxcj9, Measurement, OutputDataRate, Resolution,
+    SlaveAddr, UnscaledMeasurement, WakeUpInterruptConfig,
+};
+use hal::blocking::delay::DelayUs;
+
+#[derive(Debug)]
+enum AnySensor<I2C> {
+    G8(Kxcj9<I2C, ic::G8Device>),
+    G16(Kxcj9<I2C, ic::G16Device>),
+}
+
+/// Driver for a device variant selected at runtime
+///
+/// This wraps the driver of either device kind behind a single type so
+/// that code supporting several variants does not need to be generic.
+/// Variant-specific methods like `set_scale()` are available through
+/// [`as_g8_mut()`](#method.as_g8_mut) and [`as_g16_mut()`](#method.as_g16_mut).
+#[derive(Debug)]
+pub struct AnyKxcj9<I2C> {
+    variant: DeviceVariant,
+    sensor: AnySensor<I2C>,
+}
+
+macro_rules! delegate {
+    ($sensor:expr, $s:ident => $e:expr) => {
+        match $sensor {
+            AnySensor::G8($s) => $e,
+            AnySensor::G16($s) => $e,
+        }
+    };
+}
+
+impl<I2C, E> AnyKxcj9<I2C>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+{
+    /// Create new instance of the given device variant.
+    pub fn new(i2c: I2C, address: SlaveAddr, variant: DeviceVariant) -> Self {
+        let sensor = match variant {
+            DeviceVariant::Kxcj9_1008 => AnySensor::G8(Kxcj9::new_kxcj9_1008(i2c, address)),
+            DeviceVariant::Kxcjb_1041 => AnySensor::G8(Kxcj9::new_kxcjb_1041(i2c, address)),
+            DeviceVariant::Kxcj9_1018 => AnySensor::G16(Kxcj9::new_kxcj9_1018(i2c, address)),
+        };
+        AnyKxcj9 { variant, sensor }
+    }
+
+    /// Create new instance of the variant identified by the `WHO_AM_I`
+    /// register value.
+    ///
+    /// On failure the I²C bus instance is returned together with the error.
+    /// `Err(Error::WrongDevice)` is returned if the value is unknown.
+    pub fn detect(i2c: I2C, address: SlaveAddr) -> Result<Self, (Error<E>, I2C)> {
+        // All variants share the register map.
+        let mut sensor = Kxcj9::new_kxcj9_1008(i2c, address);
+        let who_am_i = match sensor.who_am_i() {
+            Ok(who_am_i) => who_am_i,
+            Err(e) => return Err((e, sensor.destroy())),
+        };
+        match DeviceVariant::from_who_am_i(who_am_i) {
+            Some(variant) => Ok(Self::new(sensor.destroy(), address, variant)),
+            None => Err((Error::WrongDevice { who_am_i }, sensor.destroy())),
+        }
+    }
+
+    /// Destroy driver instance, return I²C bus instance.
+    pub fn destroy(self) -> I2C {
+        delegate!(self.sensor, s => s.destroy())
+    }
+
+    /// Device variant.
+    pub fn variant(&self) -> DeviceVariant {
+        self.variant
+    }
+
+    /// Capabilities of the device variant.
+    pub fn capabilities(&self) -> Capabilities {
+        self.variant.capabilities()
+    }
+
+    /// Driver of a KXCJ9-1008 or KXCJB-1041 device.
+    pub fn as_g8(&self) -> Option<&Kxcj9<I2C, ic::G8Device>> {
+        match &self.sensor {
+            AnySensor::G8(s) => Some(s),
+            AnySensor::G16(_) => None,
+        }
+    }
+
+    /// Driver of a KXCJ9-1018 device.
+    pub fn as_g16(&self) -> Option<&Kxcj9<I2C, ic::G16Device>> {
+        match &self.sensor {
+            AnySensor::G8(_) => None,
+            AnySensor::G16(s) => Some(s),
+        }
+    }
+
+    /// Mutable driver of a KXCJ9-1008 or KXCJB-1041 device.
+    pub fn as_g8_mut(&mut self) -> Option<&mut Kxcj9<I2C, ic::G8Device>> {
+        match &mut self.sensor {
+            AnySensor::G8(s) => Some(s),
+            AnySensor::G16(_) => None,
+        }
+    }
+
+    /// Mutable driver of a KXCJ9-1018 device.
+    pub fn as_g16_mut(&mut self) -> Option<&mut Kxcj9<I2C, ic::G16Device>> {
+        match &mut self.sensor {
+            AnySensor::G8(_) => None,
+            AnySensor::G16(s) => Some(s),
+        }
+    }
+
+    /// Get a reference to the I²C bus instance.
+    pub fn i2c(&self) -> &I2C {
+        delegate!(&self.sensor, s => s.i2c())
+    }
+
+    /// Get a mutable reference to the I²C bus instance.
+    pub fn i2c_mut(&mut self) -> &mut I2C {
+        delegate!(&mut self.sensor, s => s.i2c_mut())
+    }
+
+    /// Enable the device (starts taking measurements).
+    pub fn enable(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.enable())
+    }
+
+    /// Disable the device.
+    pub fn disable(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.disable())
+    }
+
+    /// Enable the device and wait until the first valid measurement is available.
+    pub fn enable_and_wait<D: DelayUs<u32>>(&mut self, delay: &mut D) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.enable_and_wait(delay))
+    }
+
+    /// Typical start-up time in microseconds for the current output data
+    /// rate and resolution.
+    pub fn start_up_time_us(&self) -> u32 {
+        delegate!(&self.sensor, s => s.start_up_time_us())
+    }
+
+    /// Set the standard gravity value used when converting measurements
+    /// to SI units in m/s².
+    pub fn set_standard_gravity(&mut self, standard_gravity: f32) {
+        delegate!(&mut self.sensor, s => s.set_standard_gravity(standard_gravity))
+    }
+
+    /// Read acceleration sensor data scaled to the configured G range.
+    pub fn read(&mut self) -> Result<Measurement, Error<E>> {
+        delegate!(&mut self.sensor, s => s.read())
+    }
+
+    /// Read acceleration sensor data in SI units (m/s²).
+    pub fn read_m_s2(&mut self) -> Result<Measurement, Error<E>> {
+        delegate!(&mut self.sensor, s => s.read_m_s2())
+    }
+
+    /// Read unscaled acceleration sensor data.
+    pub fn read_unscaled(&mut self) -> Result<UnscaledMeasurement, Error<E>> {
+        delegate!(&mut self.sensor, s => s.read_unscaled())
+    }
+
+    /// Read acceleration sensor data scaled to the configured G range only
+    /// if a new measurement is available.
+    pub fn read_new(&mut self) -> nb::Result<Measurement, Error<E>> {
+        delegate!(&mut self.sensor, s => s.read_new())
+    }
+
+    /// Read unscaled acceleration sensor data only if a new measurement
+    /// is available.
+    pub fn read_unscaled_new(&mut self) -> nb::Result<UnscaledMeasurement, Error<E>> {
+        delegate!(&mut self.sensor, s => s.read_unscaled_new())
+    }
+
+    /// Wait for a new measurement and read it scaled to the configured G range.
+    pub fn read_new_blocking<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<Measurement, Error<E>> {
+        delegate!(&mut self.sensor, s => s.read_new_blocking(delay))
+    }
+
+    /// Wait for a new measurement and read it unscaled.
+    pub fn read_unscaled_new_blocking<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<UnscaledMeasurement, Error<E>> {
+        delegate!(&mut self.sensor, s => s.read_unscaled_new_blocking(delay))
+    }
+
+    /// Read the `WHO_AM_I` register.
+    pub fn who_am_i(&mut self) -> Result<u8, Error<E>> {
+        delegate!(&mut self.sensor, s => s.who_am_i())
+    }
+
+    /// Check that the `WHO_AM_I` register value matches the device variant.
+    ///
+    /// Returns `Err(Error::WrongDevice)` including the value read otherwise.
+    pub fn verify_who_am_i(&mut self) -> Result<(), Error<E>> {
+        let who_am_i = self.who_am_i()?;
+        if who_am_i == self.variant.who_am_i() {
+            Ok(())
+        } else {
+            Err(Error::WrongDevice { who_am_i })
+        }
+    }
+
+    /// Set resolution.
+    pub fn set_resolution(&mut self, resolution: Resolution) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.set_resolution(resolution))
+    }
+
+    /// Set output data rate.
+    pub fn set_output_data_rate(&mut self, odr: OutputDataRate) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.set_output_data_rate(odr))
+    }
+
+    /// Set the smallest G range covering the given acceleration in +/-g and
+    /// return the selected range.
+    pub fn set_range_at_least(&mut self, g: f32) -> Result<f32, Error<E>> {
+        delegate!(&mut self.sensor, s => s.set_range_at_least(g))
+    }
+
+    /// Configured G range in +/-g.
+    pub fn range_g(&self) -> f32 {
+        delegate!(&self.sensor, s => s.range_g())
+    }
+
+    /// Enable new acceleration data ready interrupt.
+    pub fn enable_data_ready_interrupt(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.enable_data_ready_interrupt())
+    }
+
+    /// Disable new acceleration data ready interrupt.
+    pub fn disable_data_ready_interrupt(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.disable_data_ready_interrupt())
+    }
+
+    /// Configure and enable wake-up motion detected interrupt.
+    pub fn enable_wake_up_interrupt(
+        &mut self,
+        config: WakeUpInterruptConfig,
+    ) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.enable_wake_up_interrupt(config))
+    }
+
+    /// Disable wake-up motion detected interrupt.
+    pub fn disable_wake_up_interrupt(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.disable_wake_up_interrupt())
+    }
+
+    /// Enable physical interrupt pin.
+    pub fn enable_interrupt_pin(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.enable_interrupt_pin())
+    }
+
+    /// Disable physical interrupt pin.
+    pub fn disable_interrupt_pin(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.disable_interrupt_pin())
+    }
+
+    /// Set physical interrupt pin polarity.
+    pub fn set_interrupt_pin_polarity(
+        &mut self,
+        polarity: InterruptPinPolarity,
+    ) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.set_interrupt_pin_polarity(polarity))
+    }
+
+    /// Set physical interrupt pin latching behavior.
+    pub fn set_interrupt_pin_latching(
+        &mut self,
+        latching: InterruptPinLatching,
+    ) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.set_interrupt_pin_latching(latching))
+    }
+
+    /// Check if any interrupt has happened.
+    pub fn has_interrupt_happened(&mut self) -> Result<bool, Error<E>> {
+        delegate!(&mut self.sensor, s => s.has_interrupt_happened())
+    }
+
+    /// Read interrupt source information.
+    pub fn read_interrupt_info(&mut self) -> Result<InterruptInfo, Error<E>> {
+        delegate!(&mut self.sensor, s => s.read_interrupt_info())
+    }
+
+    /// Read the interrupt sources and return the events that happened.
+    pub fn poll_events(&mut self) -> Result<Events, Error<E>> {
+        delegate!(&mut self.sensor, s => s.poll_events())
+    }
+
+    /// Read the interrupt sources, return the events that happened and
+    /// clear the interrupts afterwards.
+    pub fn poll_events_and_clear(&mut self) -> Result<Events, Error<E>> {
+        delegate!(&mut self.sensor, s => s.poll_events_and_clear())
+    }
+
+    /// Clear interrupts.
+    pub fn clear_interrupts(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.clear_interrupts())
+    }
+
+    /// Perform software reset.
+    pub fn reset(&mut self) -> nb::Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.reset())
+    }
+
+    /// Perform software reset and wait for it to finish.
+    pub fn reset_blocking<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.reset_blocking(delay, timeout_ms))
+    }
+
+    /// Perform a digital communication self-test.
+    pub fn communication_self_test(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.communication_self_test())
+    }
+
+    /// Enable the MEMS self-test function.
+    pub fn enable_mems_self_test(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.enable_mems_self_test())
+    }
+
+    /// Disable the MEMS self-test function.
+    pub fn disable_mems_self_test(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.disable_mems_self_test())
+    }
+
+    /// Compare the configuration registers of the device with the
+    /// configuration cached in the driver.
+    pub fn verify_config(&mut self) -> Result<ConfigCheck, Error<E>> {
+        delegate!(&mut self.sensor, s => s.verify_config())
+    }
+
+    /// Write the complete configuration cached in the driver to the device.
+    pub fn restore_config(&mut self) -> Result<(), Error<E>> {
+        delegate!(&mut self.sensor, s => s.restore_config())
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    i2c, nb, Axis, Direction, Error, Event, InterruptInfo, Kxcj9, OutputDataRate, Resolution,
+    ScaledDevice, UnscaledMeasurement, WakeUpInterruptConfig,
+};
+use hal::blocking::delay::DelayUs;
+
+/// Motion-triggered burst capture configuration
+#[derive(Debug, Clone, Copy)]
+pub struct BurstCaptureConfig {
+    /// Wake-up interrupt configuration used to detect motion.
+    pub wake_up: WakeUpInterruptConfig,
+    /// Output data rate while waiting for wake-up motion.
+    pub idle_data_rate: OutputDataRate,
+    /// Output data rate while capturing the burst.
+    pub burst_data_rate: OutputDataRate,
+}
+
+impl Default for BurstCaptureConfig {
+    fn default() -> Self {
+        BurstCaptureConfig {
+            wake_up: WakeUpInterruptConfig::default(),
+            idle_data_rate: OutputDataRate::Hz0_781,
+            burst_data_rate: OutputDataRate::Hz800,
+        }
+    }
+}
+
+/// Information about a captured burst
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Burst {
+    /// First axis and direction along which wake-up motion was detected.
+    pub trigger: Option<(Axis, Direction)>,
+    /// Interrupt source information which triggered the burst.
+    pub info: InterruptInfo,
+}
+
+/// Motion-triggered burst capture
+///
+/// The device idles at a low output data rate with the wake-up interrupt
+/// armed. Once wake-up motion is detected, a burst of samples is captured
+/// at a high output data rate into a caller-provided buffer, after which
+/// the idle configuration is restored.
+///
+/// The state machine does not own the driver. Call [`arm()`] once and
+/// then [`capture()`] until a burst is returned.
+///
+/// [`arm()`]: #method.arm
+/// [`capture()`]: #method.capture
+#[derive(Debug, Clone)]
+pub struct BurstCapture {
+    config: BurstCaptureConfig,
+}
+
+impl BurstCapture {
+    /// Create a new burst capture. The device is not configured until
+    /// [`arm()`](#method.arm) is called.
+    pub fn new(config: BurstCaptureConfig) -> Self {
+        BurstCapture { config }
+    }
+
+    /// Configure the device for idling, arm the wake-up interrupt and
+    /// enable the device.
+    pub fn arm<I2C, E, IC>(&mut self, sensor: &mut Kxcj9<I2C, IC>) -> Result<(), Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+    {
+        sensor.set_output_data_rate(self.config.idle_data_rate)?;
+        sensor.enable_wake_up_interrupt(self.config.wake_up)?;
+        sensor.clear_interrupts()?;
+        sensor.enable()
+    }
+
+    /// Capture a burst of samples filling the buffer if wake-up motion
+    /// was detected.
+    ///
+    /// This method offers a non-blocking interface. If no wake-up motion
+    /// was detected this returns `nb::Error::WouldBlock`. Otherwise, the
+    /// data ready interrupt is enabled, the burst output data rate is set and
+    /// each sample is read as soon as the data ready flag reports a new
+    /// measurement, polling with the delay provider. See
+    /// [`read_unscaled_new_blocking()`]. Afterwards the idle output data rate,
+    /// the resolution and the data ready interrupt setting are restored and
+    /// the interrupts are cleared. This also happens if capturing fails, in
+    /// which case the first error is returned.
+    ///
+    /// [`read_unscaled_new_blocking()`]: struct.Kxcj9.html#method.read_unscaled_new_blocking
+    pub fn capture<I2C, E, IC, D>(
+        &mut self,
+        sensor: &mut Kxcj9<I2C, IC>,
+        delay: &mut D,
+        buffer: &mut [UnscaledMeasurement],
+    ) -> nb::Result<Burst, Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+        D: DelayUs<u32>,
+    {
+        let info = sensor.read_interrupt_info()?;
+        if !info.wake_up {
+            return Err(nb::Error::WouldBlock);
+        }
+        let trigger = info.events().find_map(|event| match event {
+            Event::Motion { axis, direction } => Some((axis, direction)),
+            _ => None,
+        });
+        let resolution = sensor.resolution();
+        let data_ready_enabled = sensor.is_data_ready_interrupt_enabled();
+        let sampled = self.sample(sensor, delay, buffer, data_ready_enabled);
+        let restored = self.restore(sensor, resolution, data_ready_enabled);
+        sampled.and(restored)?;
+        Ok(Burst { trigger, info })
+    }
+
+    fn sample<I2C, E, IC, D>(
+        &self,
+        sensor: &mut Kxcj9<I2C, IC>,
+        delay: &mut D,
+        buffer: &mut [UnscaledMeasurement],
+        data_ready_enabled: bool,
+    ) -> Result<(), Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+        D: DelayUs<u32>,
+    {
+        if !data_ready_enabled {
+            sensor.enable_data_ready_interrupt()?;
+        }
+        sensor.set_output_data_rate(self.config.burst_data_rate)?;
+        // Drop any stale data ready flag from the idle configuration.
+        sensor.clear_interrupts()?;
+        for sample in buffer.iter_mut() {
+            *sample = sensor.read_unscaled_new_blocking(delay)?;
+        }
+        Ok(())
+    }
+
+    // Every step is attempted even if a previous one failed so that the
+    // device is left as close as possible to the idle configuration.
+    fn restore<I2C, E, IC>(
+        &self,
+        sensor: &mut Kxcj9<I2C, IC>,
+        resolution: Resolution,
+        data_ready_enabled: bool,
+    ) -> Result<(), Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+    {
+        let data_rate = sensor.set_output_data_rate(self.config.idle_data_rate);
+        let resolution = if sensor.resolution() != resolution {
+            sensor.set_resolution(resolution)
+        } else {
+            Ok(())
+        };
+        let data_ready = if !data_ready_enabled {
+            sensor.disable_data_ready_interrupt()
+        } else {
+            Ok(())
+        };
+        let cleared = sensor.clear_interrupts();
+        data_rate.and(resolution).and(data_ready).and(cleared)
+    }
+}
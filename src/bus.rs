@@ -0,0 +1,246 @@
+//! I²C bus sharing
+//!
+//! These proxies implement the `embedded-hal` blocking I²C traits on top
+//! of a bus shared with other devices, so that several drivers can own
+//! a proxy each.
+
+use crate::Kxcj9;
+use core::cell::RefCell;
+use core::marker::PhantomData;
+use hal::blocking::i2c::{Read, Write, WriteRead};
+
+/// I²C bus proxy sharing the bus through a `RefCell`
+///
+/// This is suitable for sharing a bus within a single execution context.
+/// The bus is borrowed for the duration of each transaction.
+#[derive(Debug)]
+pub struct RefCellDevice<'a, I2C> {
+    bus: &'a RefCell<I2C>,
+}
+
+impl<'a, I2C> RefCellDevice<'a, I2C> {
+    /// Create a new proxy for the shared bus.
+    pub fn new(bus: &'a RefCell<I2C>) -> Self {
+        RefCellDevice { bus }
+    }
+}
+
+impl<'a, I2C: Read> Read for RefCellDevice<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.bus.borrow_mut().read(address, buffer)
+    }
+}
+
+impl<'a, I2C: Write> Write for RefCellDevice<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.bus.borrow_mut().write(address, bytes)
+    }
+}
+
+impl<'a, I2C: WriteRead> WriteRead for RefCellDevice<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.bus.borrow_mut().write_read(address, bytes, buffer)
+    }
+}
+
+/// I²C bus proxy sharing the bus through a critical section mutex
+///
+/// This is suitable for sharing a bus between interrupt handlers and the
+/// main program. Each transaction is executed inside a critical section.
+#[cfg(feature = "critical-section")]
+#[derive(Debug)]
+pub struct CriticalSectionDevice<'a, I2C> {
+    bus: &'a critical_section::Mutex<RefCell<I2C>>,
+}
+
+#[cfg(feature = "critical-section")]
+impl<'a, I2C> CriticalSectionDevice<'a, I2C> {
+    /// Create a new proxy for the shared bus.
+    pub fn new(bus: &'a critical_section::Mutex<RefCell<I2C>>) -> Self {
+        CriticalSectionDevice { bus }
+    }
+}
+
+#[cfg(feature = "critical-section")]
+impl<'a, I2C: Read> Read for CriticalSectionDevice<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        critical_section::with(|cs| self.bus.borrow_ref_mut(cs).read(address, buffer))
+    }
+}
+
+#[cfg(feature = "critical-section")]
+impl<'a, I2C: Write> Write for CriticalSectionDevice<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        critical_section::with(|cs| self.bus.borrow_ref_mut(cs).write(address, bytes))
+    }
+}
+
+#[cfg(feature = "critical-section")]
+impl<'a, I2C: WriteRead> WriteRead for CriticalSectionDevice<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        critical_section::with(|cs| {
+            self.bus
+                .borrow_ref_mut(cs)
+                .write_read(address, bytes, buffer)
+        })
+    }
+}
+
+/// I²C bus proxy sharing the bus through a `std::sync::Mutex`
+///
+/// This is suitable for sharing a bus between threads.
+///
+/// # Panics
+///
+/// Transactions panic if the mutex is poisoned.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct MutexDevice<'a, I2C> {
+    bus: &'a std::sync::Mutex<I2C>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, I2C> MutexDevice<'a, I2C> {
+    /// Create a new proxy for the shared bus.
+    pub fn new(bus: &'a std::sync::Mutex<I2C>) -> Self {
+        MutexDevice { bus }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, I2C: Read> Read for MutexDevice<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.bus.lock().unwrap().read(address, buffer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, I2C: Write> Write for MutexDevice<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.bus.lock().unwrap().write(address, bytes)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, I2C: WriteRead> WriteRead for MutexDevice<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.bus.lock().unwrap().write_read(address, bytes, buffer)
+    }
+}
+
+/// I²C bus borrowed for the duration of [`Kxcj9::with_bus()`]
+///
+/// [`Kxcj9::with_bus()`]: ../struct.Kxcj9.html#method.with_bus
+#[derive(Debug)]
+pub struct BorrowedBus<'a, I2C> {
+    bus: &'a mut I2C,
+}
+
+impl<'a, I2C: Write> Write for BorrowedBus<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.bus.write(address, bytes)
+    }
+}
+
+impl<'a, I2C: WriteRead> WriteRead for BorrowedBus<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.bus.write_read(address, bytes, buffer)
+    }
+}
+
+/// Marker for a driver which does not own the I²C bus
+///
+/// See [`Kxcj9::detach()`](../struct.Kxcj9.html#method.detach).
+#[derive(Debug, Clone, Copy)]
+pub struct Detached(pub(crate) ());
+
+impl<I2C, IC> Kxcj9<I2C, IC> {
+    /// Separate the driver state from the I²C bus.
+    ///
+    /// The detached driver keeps the cached configuration and can be used
+    /// with a bus borrowed for each call. See [`with_bus()`](#method.with_bus).
+    pub fn detach(self) -> (Kxcj9<Detached, IC>, I2C) {
+        (self.with_i2c(Detached(())), self.i2c)
+    }
+
+    fn with_i2c<I2C2>(&self, i2c: I2C2) -> Kxcj9<I2C2, IC> {
+        Kxcj9 {
+            i2c,
+            address: self.address,
+            ctrl1: self.ctrl1,
+            ctrl2: self.ctrl2,
+            int_ctrl1: self.int_ctrl1,
+            data_ctrl: self.data_ctrl,
+            int_ctrl2: self.int_ctrl2,
+            wakeup_timer: self.wakeup_timer,
+            wakeup_threshold: self.wakeup_threshold,
+            was_reset_started: self.was_reset_started,
+            standard_gravity: self.standard_gravity,
+            _ic: PhantomData,
+        }
+    }
+}
+
+impl<IC> Kxcj9<Detached, IC> {
+    /// Give the driver ownership of the I²C bus again.
+    pub fn attach<I2C>(self, i2c: I2C) -> Kxcj9<I2C, IC> {
+        self.with_i2c(i2c)
+    }
+
+    /// Use the driver with an I²C bus borrowed for the duration of the call.
+    ///
+    /// Any configuration change done in the closure is kept in the
+    /// detached driver.
+    pub fn with_bus<I2C, E, F, R>(&mut self, i2c: &mut I2C, f: F) -> R
+    where
+        I2C: WriteRead<Error = E> + Write<Error = E>,
+        F: FnOnce(&mut Kxcj9<BorrowedBus<'_, I2C>, IC>) -> R,
+    {
+        let mut sensor = self.with_i2c(BorrowedBus { bus: i2c });
+        let result = f(&mut sensor);
+        *self = sensor.with_i2c(Detached(()));
+        result
+    }
+}
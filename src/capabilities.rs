@@ -0,0 +1,161 @@
+use crate::timing::WAKE_UP_DATA_RATES;
+use crate::{i2c, DeviceVariant, Kxcj9, OutputDataRate, ScaledDevice, WakeUpOutputDataRate};
+
+const OUTPUT_DATA_RATES: [OutputDataRate; 12] = [
+    OutputDataRate::Hz0_781,
+    OutputDataRate::Hz1_563,
+    OutputDataRate::Hz3_125,
+    OutputDataRate::Hz6_25,
+    OutputDataRate::Hz12_5,
+    OutputDataRate::Hz25,
+    OutputDataRate::Hz50,
+    OutputDataRate::Hz100,
+    OutputDataRate::Hz200,
+    OutputDataRate::Hz400,
+    OutputDataRate::Hz800,
+    OutputDataRate::Hz1600,
+];
+
+/// Capabilities of a device variant
+///
+/// The `WHO_AM_I` register value is not part of the capabilities because
+/// the KXCJ9-1008 and KXCJB-1041 share the same capabilities and device kind
+/// but report different values. It is available per variant through
+/// [`DeviceVariant::who_am_i()`](enum.DeviceVariant.html#method.who_am_i).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capabilities {
+    /// Full-scale ranges in +/-g in ascending order.
+    pub ranges_g: [f32; 3],
+    /// Bits per measurement in low resolution.
+    pub low_resolution_bits: u8,
+    /// Bits per measurement in high resolution.
+    pub high_resolution_bits: u8,
+    /// Bits per measurement in the full power setting of the largest range.
+    pub full_power_bits: u8,
+    /// Supported output data rates in ascending order.
+    pub data_rates: &'static [OutputDataRate],
+    /// Supported wake-up function output data rates in ascending order.
+    pub wake_up_data_rates: &'static [WakeUpOutputDataRate],
+    /// Wake-up threshold resolution in g per LSB.
+    pub wake_up_threshold_lsb_g: f32,
+    /// Maximum wake-up threshold in g.
+    pub wake_up_threshold_max_g: f32,
+}
+
+impl Capabilities {
+    /// Capabilities of the KXCJ9-1008.
+    pub const KXCJ9_1008: Capabilities = Capabilities {
+        ranges_g: [2.0, 4.0, 8.0],
+        low_resolution_bits: 8,
+        high_resolution_bits: 12,
+        full_power_bits: 14,
+        data_rates: &OUTPUT_DATA_RATES,
+        wake_up_data_rates: &WAKE_UP_DATA_RATES,
+        wake_up_threshold_lsb_g: 1.0 / 16.0,
+        wake_up_threshold_max_g: 8.0,
+    };
+
+    /// Capabilities of the KXCJ9-1018.
+    pub const KXCJ9_1018: Capabilities = Capabilities {
+        ranges_g: [4.0, 8.0, 16.0],
+        wake_up_threshold_lsb_g: 1.0 / 8.0,
+        wake_up_threshold_max_g: 16.0,
+        ..Capabilities::KXCJ9_1008
+    };
+
+    /// Capabilities of the KXCJB-1041.
+    pub const KXCJB_1041: Capabilities = Capabilities::KXCJ9_1008;
+
+    /// Largest full-scale range in +/-g.
+    pub fn max_range_g(&self) -> f32 {
+        self.ranges_g[self.ranges_g.len() - 1]
+    }
+
+    /// Sensitivity in g per LSB for a full-scale range in +/-g and a
+    /// number of bits per measurement.
+    ///
+    /// Returns `None` if the number of bits is not in the range `[1-16]`.
+    pub fn sensitivity_g_per_lsb(range_g: f32, bits: u8) -> Option<f32> {
+        if bits == 0 || bits > 16 {
+            return None;
+        }
+        Some(range_g / f32::from(1_u16 << (bits - 1)))
+    }
+}
+
+impl DeviceVariant {
+    /// Capabilities of the variant.
+    pub fn capabilities(self) -> Capabilities {
+        match self {
+            DeviceVariant::Kxcj9_1008 => Capabilities::KXCJ9_1008,
+            DeviceVariant::Kxcj9_1018 => Capabilities::KXCJ9_1018,
+            DeviceVariant::Kxcjb_1041 => Capabilities::KXCJB_1041,
+        }
+    }
+}
+
+impl<I2C, E, IC> Kxcj9<I2C, IC>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    IC: ScaledDevice,
+{
+    /// Capabilities of the device kind.
+    ///
+    /// KXCJ9-1008 and KXCJB-1041 devices share the same capabilities.
+    pub fn capabilities(&self) -> Capabilities {
+        IC::CAPABILITIES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ic;
+
+    #[test]
+    fn sensitivity() {
+        assert_eq!(
+            Some(2.0 / 128.0),
+            Capabilities::sensitivity_g_per_lsb(2.0, 8)
+        );
+        assert_eq!(
+            Some(16.0 / 8192.0),
+            Capabilities::sensitivity_g_per_lsb(16.0, 14)
+        );
+        assert_eq!(Some(1.0), Capabilities::sensitivity_g_per_lsb(1.0, 1));
+        assert_eq!(None, Capabilities::sensitivity_g_per_lsb(2.0, 0));
+        assert_eq!(None, Capabilities::sensitivity_g_per_lsb(2.0, 17));
+    }
+
+    #[test]
+    fn max_range() {
+        assert_eq!(8.0, Capabilities::KXCJB_1041.max_range_g());
+        assert_eq!(16.0, Capabilities::KXCJ9_1018.max_range_g());
+    }
+
+    #[test]
+    fn device_kinds_match_variants() {
+        for variant in <ic::G8Device as ScaledDevice>::VARIANTS {
+            assert_eq!(ic::G8Device::CAPABILITIES, variant.capabilities());
+        }
+        for variant in <ic::G16Device as ScaledDevice>::VARIANTS {
+            assert_eq!(ic::G16Device::CAPABILITIES, variant.capabilities());
+        }
+    }
+
+    fn wake_up_threshold_matches<IC: ScaledDevice>() {
+        let caps = IC::CAPABILITIES;
+        let max = IC::get_wake_up_threshold::<()>(caps.wake_up_threshold_max_g).unwrap();
+        assert_eq!(
+            caps.wake_up_threshold_max_g,
+            f32::from(max) * caps.wake_up_threshold_lsb_g
+        );
+        IC::get_wake_up_threshold::<()>(caps.wake_up_threshold_max_g + 0.1).unwrap_err();
+    }
+
+    #[test]
+    fn wake_up_threshold_matches_conversion() {
+        wake_up_threshold_matches::<ic::G8Device>();
+        wake_up_threshold_matches::<ic::G16Device>();
+    }
+}
@@ -0,0 +1,337 @@
+use crate::{i2c, Axis, Direction, Error, InterruptInfo, Kxcj9, ScaledDevice, UnscaledMeasurement};
+
+/// Condition which triggers the capture of a window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptureTrigger {
+    /// Magnitude of the unscaled measurement greater or equal to this value.
+    Magnitude(f32),
+    /// Unscaled measurement along an axis beyond a level.
+    Level {
+        /// Axis to compare
+        axis: Axis,
+        /// `Positive`: triggers if the value is greater or equal to the level.
+        /// `Negative`: triggers if the value is lower or equal to the level.
+        direction: Direction,
+        /// Level to compare with
+        level: i16,
+    },
+    /// Wake-up interrupt.
+    ///
+    /// See [`push_with_interrupt_info()`](struct.CaptureBuffer.html#method.push_with_interrupt_info).
+    WakeUp,
+}
+
+/// Capture buffer configuration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureConfig {
+    /// Condition which triggers the capture.
+    pub trigger: CaptureTrigger,
+    /// Number of samples kept before the trigger sample.
+    pub pre_trigger: usize,
+    /// Number of samples captured after the trigger sample.
+    pub post_trigger: usize,
+}
+
+/// Captured window of samples in chronological order
+#[derive(Debug, PartialEq, Eq)]
+pub struct CaptureWindow<'a> {
+    /// Captured samples
+    pub samples: &'a [UnscaledMeasurement],
+    /// Index of the sample which fired the trigger
+    pub trigger_index: usize,
+}
+
+/// Oscilloscope-style pre-trigger capture buffer
+///
+/// Samples are continuously stored in a circular buffer. When the trigger
+/// fires, `post_trigger` further samples are stored and the window is
+/// frozen until [`rearm()`](#method.rearm) is called.
+///
+/// If the trigger fires before `pre_trigger` samples have been stored,
+/// the window contains fewer samples before the trigger.
+#[derive(Debug)]
+pub struct CaptureBuffer<'a> {
+    buffer: &'a mut [UnscaledMeasurement],
+    config: CaptureConfig,
+    head: usize,
+    len: usize,
+    remaining: Option<usize>,
+    frozen: bool,
+}
+
+impl<'a> CaptureBuffer<'a> {
+    /// Create a new capture buffer using the provided storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is shorter than `pre_trigger + 1 + post_trigger`.
+    pub fn new(buffer: &'a mut [UnscaledMeasurement], config: CaptureConfig) -> Self {
+        let window_len = config.pre_trigger + 1 + config.post_trigger;
+        assert!(buffer.len() >= window_len, "capture buffer too short");
+        CaptureBuffer {
+            buffer: &mut buffer[..window_len],
+            config,
+            head: 0,
+            len: 0,
+            remaining: None,
+            frozen: false,
+        }
+    }
+
+    /// Store a sample and check the magnitude and level triggers.
+    ///
+    /// Returns `true` once the window has been captured.
+    pub fn push(&mut self, sample: UnscaledMeasurement) -> bool {
+        self.store(sample, false)
+    }
+
+    /// Store a sample and check all triggers including the wake-up
+    /// interrupt in the given interrupt source information.
+    ///
+    /// Returns `true` once the window has been captured.
+    pub fn push_with_interrupt_info(
+        &mut self,
+        sample: UnscaledMeasurement,
+        info: &InterruptInfo,
+    ) -> bool {
+        let wake_up = self.config.trigger == CaptureTrigger::WakeUp && info.wake_up;
+        self.store(sample, wake_up)
+    }
+
+    /// Read a sample from the device and store it.
+    ///
+    /// With the wake-up trigger, the interrupt source information is read
+    /// as well and the interrupts are cleared if a wake-up interrupt happened.
+    /// Returns `true` once the window has been captured.
+    pub fn sample<I2C, E, IC>(&mut self, sensor: &mut Kxcj9<I2C, IC>) -> Result<bool, Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+    {
+        if self.frozen {
+            return Ok(true);
+        }
+        let sample = sensor.read_unscaled()?;
+        if self.config.trigger == CaptureTrigger::WakeUp {
+            let info = sensor.read_interrupt_info()?;
+            if info.wake_up {
+                sensor.clear_interrupts()?;
+            }
+            Ok(self.push_with_interrupt_info(sample, &info))
+        } else {
+            Ok(self.push(sample))
+        }
+    }
+
+    /// Captured window if the capture has finished.
+    pub fn window(&self) -> Option<CaptureWindow<'_>> {
+        if self.frozen {
+            Some(CaptureWindow {
+                samples: &self.buffer[..self.len],
+                trigger_index: self.len - 1 - self.config.post_trigger,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Whether the trigger has fired.
+    pub fn is_triggered(&self) -> bool {
+        self.remaining.is_some()
+    }
+
+    /// Discard the stored samples and wait for the trigger again.
+    pub fn rearm(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.remaining = None;
+        self.frozen = false;
+    }
+
+    fn store(&mut self, sample: UnscaledMeasurement, external_trigger: bool) -> bool {
+        if self.frozen {
+            return true;
+        }
+        self.buffer[self.head] = sample;
+        self.head = (self.head + 1) % self.buffer.len();
+        if self.len < self.buffer.len() {
+            self.len += 1;
+        }
+        let remaining = match self.remaining {
+            Some(remaining) => remaining - 1,
+            None if external_trigger || self.is_level_trigger(&sample) => self.config.post_trigger,
+            None => return false,
+        };
+        self.remaining = Some(remaining);
+        if remaining == 0 {
+            self.freeze();
+        }
+        self.frozen
+    }
+
+    fn is_level_trigger(&self, sample: &UnscaledMeasurement) -> bool {
+        match self.config.trigger {
+            CaptureTrigger::Magnitude(level) => sample.magnitude() >= level,
+            CaptureTrigger::Level {
+                axis,
+                direction,
+                level,
+            } => {
+                let value = match axis {
+                    Axis::X => sample.x,
+                    Axis::Y => sample.y,
+                    Axis::Z => sample.z,
+                };
+                match direction {
+                    Direction::Positive => value >= level,
+                    Direction::Negative => value <= level,
+                }
+            }
+            CaptureTrigger::WakeUp => false,
+        }
+    }
+
+    fn freeze(&mut self) {
+        if self.len == self.buffer.len() {
+            self.buffer.rotate_left(self.head);
+        }
+        self.frozen = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(x: i16) -> UnscaledMeasurement {
+        UnscaledMeasurement { x, y: 0, z: 0 }
+    }
+
+    fn level_config(pre_trigger: usize, post_trigger: usize) -> CaptureConfig {
+        CaptureConfig {
+            trigger: CaptureTrigger::Level {
+                axis: Axis::X,
+                direction: Direction::Positive,
+                level: 100,
+            },
+            pre_trigger,
+            post_trigger,
+        }
+    }
+
+    fn xs(window: &CaptureWindow) -> [i16; 5] {
+        let mut xs = [0; 5];
+        for (x, sample) in xs.iter_mut().zip(window.samples.iter()) {
+            *x = sample.x;
+        }
+        xs
+    }
+
+    #[test]
+    fn captures_pre_and_post_trigger_samples() {
+        let mut storage = [UnscaledMeasurement::default(); 8];
+        let mut capture = CaptureBuffer::new(&mut storage, level_config(2, 2));
+        for x in 1..6 {
+            assert!(!capture.push(m(x)));
+        }
+        assert!(!capture.push(m(100)));
+        assert!(capture.is_triggered());
+        assert!(!capture.push(m(7)));
+        assert!(capture.window().is_none());
+        assert!(capture.push(m(8)));
+        assert!(capture.push(m(9)));
+        let window = capture.window().unwrap();
+        assert_eq!(5, window.samples.len());
+        assert_eq!(2, window.trigger_index);
+        assert_eq!([4, 5, 100, 7, 8], xs(&window));
+    }
+
+    #[test]
+    fn early_trigger_has_fewer_pre_trigger_samples() {
+        let mut storage = [UnscaledMeasurement::default(); 5];
+        let mut capture = CaptureBuffer::new(&mut storage, level_config(3, 1));
+        capture.push(m(1));
+        capture.push(m(150));
+        assert!(capture.push(m(2)));
+        let window = capture.window().unwrap();
+        assert_eq!(3, window.samples.len());
+        assert_eq!(1, window.trigger_index);
+    }
+
+    #[test]
+    fn can_trigger_without_post_trigger_samples() {
+        let mut storage = [UnscaledMeasurement::default(); 2];
+        let mut capture = CaptureBuffer::new(&mut storage, level_config(1, 0));
+        capture.push(m(1));
+        capture.push(m(2));
+        assert!(capture.push(m(100)));
+        let window = capture.window().unwrap();
+        assert_eq!(1, window.trigger_index);
+        assert_eq!(2, window.samples[0].x);
+    }
+
+    #[test]
+    fn can_rearm() {
+        let mut storage = [UnscaledMeasurement::default(); 2];
+        let mut capture = CaptureBuffer::new(&mut storage, level_config(1, 0));
+        assert!(capture.push(m(100)));
+        capture.rearm();
+        assert!(capture.window().is_none());
+        assert!(!capture.push(m(1)));
+    }
+
+    #[test]
+    fn magnitude_trigger() {
+        let mut storage = [UnscaledMeasurement::default(); 1];
+        let config = CaptureConfig {
+            trigger: CaptureTrigger::Magnitude(5.0),
+            pre_trigger: 0,
+            post_trigger: 0,
+        };
+        let mut capture = CaptureBuffer::new(&mut storage, config);
+        assert!(!capture.push(UnscaledMeasurement { x: 3, y: 3, z: 0 }));
+        assert!(capture.push(UnscaledMeasurement { x: 3, y: 4, z: 0 }));
+    }
+
+    #[test]
+    fn negative_level_trigger() {
+        let mut storage = [UnscaledMeasurement::default(); 1];
+        let config = CaptureConfig {
+            trigger: CaptureTrigger::Level {
+                axis: Axis::X,
+                direction: Direction::Negative,
+                level: -10,
+            },
+            pre_trigger: 0,
+            post_trigger: 0,
+        };
+        let mut capture = CaptureBuffer::new(&mut storage, config);
+        assert!(!capture.push(m(-9)));
+        assert!(capture.push(m(-10)));
+    }
+
+    #[test]
+    fn wake_up_trigger() {
+        let mut storage = [UnscaledMeasurement::default(); 1];
+        let config = CaptureConfig {
+            trigger: CaptureTrigger::WakeUp,
+            pre_trigger: 0,
+            post_trigger: 0,
+        };
+        let mut capture = CaptureBuffer::new(&mut storage, config);
+        let info = InterruptInfo::default();
+        assert!(!capture.push_with_interrupt_info(m(1000), &info));
+        let info = InterruptInfo {
+            wake_up: true,
+            ..Default::default()
+        };
+        assert!(capture.push_with_interrupt_info(m(1), &info));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_if_buffer_too_short() {
+        let mut storage = [UnscaledMeasurement::default(); 4];
+        CaptureBuffer::new(&mut storage, level_config(2, 2));
+    }
+}
@@ -1,4 +1,4 @@
-use UnscaledMeasurement;
+use crate::UnscaledMeasurement;
 
 pub fn convert_8bit(x: u8, y: u8, z: u8) -> UnscaledMeasurement {
     UnscaledMeasurement {
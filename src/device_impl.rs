@@ -1,30 +1,33 @@
-use {
+use crate::{
     conversion::{convert_12bit, convert_14bit, convert_8bit},
-    i2c, ic, nb, Config, Error, GScale16, GScale8, InterruptInfo, InterruptPinLatching,
-    InterruptPinPolarity, Kxcj9, Measurement, OutputDataRate, PhantomData, Resolution,
-    ScaledDevice, SlaveAddr, UnscaledMeasurement, WakeUpInterruptConfig, WakeUpTriggerMotion,
-    DEVICE_BASE_ADDRESS,
+    i2c, ic, nb,
+    timing::WAKE_UP_DATA_RATES,
+    Config, ConfigCheck, Error, InterruptInfo, InterruptPinLatching, InterruptPinPolarity, Kxcj9,
+    Measurement, OutputDataRate, PhantomData, Resolution, ScaledDevice, SelfTestStage,
+    SettingError, SlaveAddr, UnscaledMeasurement, WakeUpInterruptConfig, WakeUpOutputDataRate,
+    WakeUpTriggerMotion, DEVICE_BASE_ADDRESS, STANDARD_GRAVITY,
 };
+use hal::blocking::delay::DelayUs;
 
-struct Register;
+pub(crate) struct Register;
 impl Register {
-    const XOUT_L: u8 = 0x06;
-    const DCST_RESP: u8 = 0x0C;
-    const WHO_AM_I: u8 = 0x0F;
-    const INT_SOURCE1: u8 = 0x16;
-    const STATUS: u8 = 0x18;
-    const INT_REL: u8 = 0x1A;
-    const CTRL1: u8 = 0x1B;
-    const CTRL2: u8 = 0x1D;
-    const INT_CTRL1: u8 = 0x1E;
-    const INT_CTRL2: u8 = 0x1F;
-    const DATA_CTRL: u8 = 0x21;
-    const WAKEUP_TIMER: u8 = 0x29;
-    const SELF_TEST: u8 = 0x3A;
-    const WAKEUP_THRESHOLD: u8 = 0x6A;
+    pub(crate) const XOUT_L: u8 = 0x06;
+    pub(crate) const DCST_RESP: u8 = 0x0C;
+    pub(crate) const WHO_AM_I: u8 = 0x0F;
+    pub(crate) const INT_SOURCE1: u8 = 0x16;
+    pub(crate) const STATUS: u8 = 0x18;
+    pub(crate) const INT_REL: u8 = 0x1A;
+    pub(crate) const CTRL1: u8 = 0x1B;
+    pub(crate) const CTRL2: u8 = 0x1D;
+    pub(crate) const INT_CTRL1: u8 = 0x1E;
+    pub(crate) const INT_CTRL2: u8 = 0x1F;
+    pub(crate) const DATA_CTRL: u8 = 0x21;
+    pub(crate) const WAKEUP_TIMER: u8 = 0x29;
+    pub(crate) const SELF_TEST: u8 = 0x3A;
+    pub(crate) const WAKEUP_THRESHOLD: u8 = 0x6A;
 }
 
-struct BitFlags;
+pub(crate) struct BitFlags;
 impl BitFlags {
     const PC1: u8 = 0b1000_0000;
     const RES: u8 = 0b0100_0000;
@@ -32,8 +35,8 @@ impl BitFlags {
     const GSEL1: u8 = 0b0001_0000;
     const GSEL0: u8 = 0b0000_1000;
     const WUFE: u8 = 0b0000_0010;
-    const SRST: u8 = 0b1000_0000;
-    const DCST: u8 = 0b0001_0000;
+    pub(crate) const SRST: u8 = 0b1000_0000;
+    pub(crate) const DCST: u8 = 0b0001_0000;
     const INT: u8 = 0b0001_0000;
     const DRDY: u8 = 0b0001_0000;
     const WUFS: u8 = 0b0000_0010;
@@ -50,11 +53,22 @@ impl BitFlags {
 
 const DATA_CTRL_DEFAULT: u8 = 0x02;
 const INT_CTRL1_DEFAULT: u8 = 0x10;
-
-#[doc(hidden)]
+const INT_CTRL2_DEFAULT: u8 = 0x3F;
+const WAKEUP_TIMER_DEFAULT: u8 = 0;
+const WAKEUP_THRESHOLD_DEFAULT: u8 = 0x08;
+/// Time the device needs to start up after a software reset
+const RESET_START_UP_TIME_MS: u32 = 10;
+const RESET_POLL_INTERVAL_MS: u32 = 1;
+
+/// Number of bits per measurement
+///
+/// Used by [`ScaledDevice`](trait.ScaledDevice.html).
 pub enum MeasurementBits {
+    /// 8 bits (low resolution)
     _8bit,
+    /// 12 bits (high resolution)
     _12bit,
+    /// 14 bits (high resolution at the full power G range)
     _14bit,
 }
 
@@ -68,11 +82,17 @@ impl MeasurementBits {
     }
 }
 
-#[doc(hidden)]
+/// G range selection bits `GSEL1` and `GSEL0` in `CTRL1`
+///
+/// Used by [`ScaledDevice`](trait.ScaledDevice.html).
 pub enum GScaleConfig {
+    /// `GSEL1 = 0`, `GSEL0 = 0`
     _0,
+    /// `GSEL1 = 0`, `GSEL0 = 1`
     _1,
+    /// `GSEL1 = 1`, `GSEL0 = 0`
     _2,
+    /// `GSEL1 = 1`, `GSEL0 = 1`
     _3,
 }
 
@@ -93,18 +113,7 @@ where
 {
     /// Create new instance of the KXCJ9-1008 device.
     pub fn new_kxcj9_1008(i2c: I2C, address: SlaveAddr) -> Self {
-        Kxcj9 {
-            i2c,
-            address: address.addr(DEVICE_BASE_ADDRESS),
-            ctrl1: Config::default(),
-            ctrl2: Config::default(),
-            int_ctrl1: Config {
-                bits: INT_CTRL1_DEFAULT,
-            },
-            data_ctrl: DATA_CTRL_DEFAULT,
-            was_reset_started: false,
-            _ic: PhantomData,
-        }
+        Self::create(i2c, address)
     }
 
     /// Create new instance of the KXCJB-1041 device.
@@ -120,6 +129,12 @@ where
 {
     /// Create new instance of the KXCJ9-1018 device.
     pub fn new_kxcj9_1018(i2c: I2C, address: SlaveAddr) -> Self {
+        Self::create(i2c, address)
+    }
+}
+
+impl<I2C, IC> Kxcj9<I2C, IC> {
+    pub(crate) fn create(i2c: I2C, address: SlaveAddr) -> Self {
         Kxcj9 {
             i2c,
             address: address.addr(DEVICE_BASE_ADDRESS),
@@ -129,7 +144,11 @@ where
                 bits: INT_CTRL1_DEFAULT,
             },
             data_ctrl: DATA_CTRL_DEFAULT,
+            int_ctrl2: INT_CTRL2_DEFAULT,
+            wakeup_timer: WAKEUP_TIMER_DEFAULT,
+            wakeup_threshold: WAKEUP_THRESHOLD_DEFAULT,
             was_reset_started: false,
+            standard_gravity: STANDARD_GRAVITY,
             _ic: PhantomData,
         }
     }
@@ -144,6 +163,22 @@ where
         self.i2c
     }
 
+    /// Get a reference to the I²C bus instance.
+    ///
+    /// This can be used to access diagnostics of I²C wrappers.
+    /// See [`RetryingI2c`](struct.RetryingI2c.html).
+    pub fn i2c(&self) -> &I2C {
+        &self.i2c
+    }
+
+    /// Get a mutable reference to the I²C bus instance.
+    ///
+    /// Communicating with the device directly can put it in a state which
+    /// does not match the configuration cached in the driver.
+    pub fn i2c_mut(&mut self) -> &mut I2C {
+        &mut self.i2c
+    }
+
     /// Enable the device (starts taking measurements).
     pub fn enable(&mut self) -> Result<(), Error<E>> {
         let config = self.ctrl1.with_high(BitFlags::PC1);
@@ -155,6 +190,14 @@ where
         let config = self.ctrl1.with_low(BitFlags::PC1);
         self.update_ctrl1(config)
     }
+
+    /// Set the standard gravity value used when converting measurements
+    /// to SI units in m/s². (default: [`STANDARD_GRAVITY`])
+    ///
+    /// [`STANDARD_GRAVITY`]: constant.STANDARD_GRAVITY.html
+    pub fn set_standard_gravity(&mut self, standard_gravity: f32) {
+        self.standard_gravity = standard_gravity;
+    }
 }
 
 impl<I2C, E, IC> Kxcj9<I2C, IC>
@@ -171,6 +214,20 @@ where
             GScaleConfig::from_ctrl1(self.ctrl1),
         ))
     }
+
+    /// Read acceleration sensor data in SI units (m/s²).
+    ///
+    /// The measurement is scaled to the configured G range and then
+    /// converted using the configured standard gravity value.
+    /// See [`set_standard_gravity()`](#method.set_standard_gravity).
+    pub fn read_m_s2(&mut self) -> Result<Measurement, Error<E>> {
+        let m = self.read()?;
+        Ok(Measurement {
+            x: m.x * self.standard_gravity,
+            y: m.y * self.standard_gravity,
+            z: m.z * self.standard_gravity,
+        })
+    }
 }
 
 impl<I2C, E, IC> Kxcj9<I2C, IC>
@@ -200,6 +257,81 @@ where
         Ok(m)
     }
 
+    /// Read acceleration sensor data scaled to the configured G range only
+    /// if a new measurement is available.
+    ///
+    /// This checks the new data ready flag, which requires the data ready
+    /// interrupt to be enabled. See [`enable_data_ready_interrupt()`](#method.enable_data_ready_interrupt).
+    /// If no new measurement is available this returns `nb::Error::WouldBlock`.
+    ///
+    /// Reading the measurement clears the data ready flag. Other pending
+    /// interrupt information like wake-up events is left untouched.
+    pub fn read_new(&mut self) -> nb::Result<Measurement, Error<E>> {
+        let unscaled = self.read_unscaled_new()?;
+        Ok(IC::get_scaled(
+            unscaled,
+            self.get_measurement_bits(),
+            GScaleConfig::from_ctrl1(self.ctrl1),
+        ))
+    }
+
+    /// Read unscaled acceleration sensor data only if a new measurement
+    /// is available.
+    ///
+    /// See [`read_new()`](#method.read_new).
+    pub fn read_unscaled_new(&mut self) -> nb::Result<UnscaledMeasurement, Error<E>> {
+        let int_source1 = self.read_register(Register::INT_SOURCE1)?;
+        if !is_high(int_source1, BitFlags::DRDY) {
+            return Err(nb::Error::WouldBlock);
+        }
+        Ok(self.read_unscaled()?)
+    }
+
+    /// Wait for a new measurement and read it scaled to the configured G range.
+    ///
+    /// The new data ready flag is polled four times per measurement period
+    /// of the configured output data rate using the delay provider.
+    /// Returns `Err(Error::ReadTimeout)` if no new measurement is available
+    /// after the start-up time plus one measurement period of the configured
+    /// output data rate. See [`read_new()`](#method.read_new).
+    pub fn read_new_blocking<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<Measurement, Error<E>> {
+        let unscaled = self.read_unscaled_new_blocking(delay)?;
+        Ok(IC::get_scaled(
+            unscaled,
+            self.get_measurement_bits(),
+            GScaleConfig::from_ctrl1(self.ctrl1),
+        ))
+    }
+
+    /// Wait for a new measurement and read it unscaled.
+    ///
+    /// See [`read_new_blocking()`](#method.read_new_blocking).
+    pub fn read_unscaled_new_blocking<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<UnscaledMeasurement, Error<E>> {
+        let data_rate = self.output_data_rate();
+        let poll_interval_us = data_rate.period_us() / 4;
+        let timeout_us = data_rate.start_up_time_us(self.resolution()) + data_rate.period_us();
+        let mut elapsed_us = 0;
+        loop {
+            match self.read_unscaled_new() {
+                Ok(measurement) => return Ok(measurement),
+                Err(nb::Error::Other(e)) => return Err(e),
+                Err(nb::Error::WouldBlock) => {
+                    if elapsed_us >= timeout_us {
+                        return Err(Error::ReadTimeout);
+                    }
+                    delay.delay_us(poll_interval_us);
+                    elapsed_us += poll_interval_us;
+                }
+            }
+        }
+    }
+
     fn get_measurement_bits(&self) -> MeasurementBits {
         let is_low_res = !self.ctrl1.is_high(BitFlags::RES);
         if is_low_res {
@@ -225,14 +357,17 @@ where
 
     /// Set resolution.
     ///
-    /// Returns `Err(Error::InvalidSetting)` if setting `Resolution::Low` but the
-    /// configured output data rate is greater or equal to 400 Hz.
+    /// Returns `Err(Error::InvalidSetting(SettingError::LowResolutionAtHighDataRate))`
+    /// if setting `Resolution::Low` but the configured output data rate is
+    /// greater or equal to 400 Hz.
     pub fn set_resolution(&mut self, resolution: Resolution) -> Result<(), Error<E>> {
         let config;
         match resolution {
             Resolution::Low => {
                 if self.output_data_rate_greater_eq_400hz()? {
-                    return Err(Error::InvalidSetting);
+                    return Err(Error::InvalidSetting(
+                        SettingError::LowResolutionAtHighDataRate,
+                    ));
                 } else {
                     config = self.ctrl1.with_low(BitFlags::RES);
                 }
@@ -298,9 +433,9 @@ where
         &mut self,
         config: WakeUpInterruptConfig,
     ) -> Result<(), Error<E>> {
-        use WakeUpOutputDataRate as ODR;
+        use crate::WakeUpOutputDataRate as ODR;
         if config.fault_count == 0 {
-            return Err(Error::InvalidSetting);
+            return Err(Error::InvalidSetting(SettingError::ZeroFaultCount));
         }
         let threshold = IC::get_wake_up_threshold(config.threshold)?;
 
@@ -319,10 +454,13 @@ where
         let ctrl1 = self.ctrl1.with_high(BitFlags::WUFE);
         self.prepare_ctrl1_to_change_settings()?;
         self.write_register(Register::INT_CTRL2, int_ctrl2)?;
+        self.int_ctrl2 = int_ctrl2;
         self.write_register(Register::CTRL2, ctrl2.bits)?;
         self.ctrl2 = ctrl2;
         self.write_register(Register::WAKEUP_TIMER, config.fault_count)?;
+        self.wakeup_timer = config.fault_count;
         self.write_register(Register::WAKEUP_THRESHOLD, threshold)?;
+        self.wakeup_threshold = threshold;
         self.update_ctrl1(ctrl1)
     }
 
@@ -385,39 +523,6 @@ where
         self.update_ctrl1(previous_ctrl1)
     }
 
-    /// Check if any interrupt has happened.
-    pub fn has_interrupt_happened(&mut self) -> Result<bool, Error<E>> {
-        let status = self.read_register(Register::STATUS)?;
-        Ok((status & BitFlags::INT) != 0)
-    }
-
-    /// Read interrupt source information.
-    pub fn read_interrupt_info(&mut self) -> Result<InterruptInfo, Error<E>> {
-        let mut data = [0; 2];
-        self.i2c
-            .write_read(self.address, &[Register::INT_SOURCE1], &mut data)
-            .map_err(Error::I2C)?;
-        let info = InterruptInfo {
-            data_ready: is_high(data[0], BitFlags::DRDY),
-            wake_up: is_high(data[0], BitFlags::WUFS),
-            wake_up_x_positive: is_high(data[1], BitFlags::XPWU),
-            wake_up_x_negative: is_high(data[1], BitFlags::XNWU),
-            wake_up_y_positive: is_high(data[1], BitFlags::YPWU),
-            wake_up_y_negative: is_high(data[1], BitFlags::YNWU),
-            wake_up_z_positive: is_high(data[1], BitFlags::ZPWU),
-            wake_up_z_negative: is_high(data[1], BitFlags::ZNWU),
-        };
-        Ok(info)
-    }
-
-    /// Clear interrupts.
-    ///
-    /// This clears all interrupt source registers and changes the physical
-    /// interrupt pin to its inactive state.
-    pub fn clear_interrupts(&mut self) -> Result<(), Error<E>> {
-        self.read_register(Register::INT_REL).and(Ok(()))
-    }
-
     /// Perform software reset.
     ///
     /// This method offers a non-blocking interface. While the reset is in
@@ -430,42 +535,95 @@ where
             self.was_reset_started = false;
             Ok(())
         } else {
-            self.i2c
-                .write(self.address, &[Register::CTRL2, BitFlags::SRST])
-                .map_err(Error::I2C)
-                .map_err(nb::Error::Other)?;
-            self.ctrl1 = Config::default();
-            self.ctrl2 = Config::default();
-            self.data_ctrl = DATA_CTRL_DEFAULT;
-            self.was_reset_started = true;
+            self.start_reset().map_err(nb::Error::Other)?;
             Err(nb::Error::WouldBlock)
         }
     }
 
+    /// Perform software reset and wait for it to finish.
+    ///
+    /// After triggering the reset this waits for the device start-up time
+    /// and then polls the reset status every millisecond using the delay
+    /// provider. Returns `Err(Error::ResetTimeout)` if the reset has not
+    /// finished after `timeout_ms` milliseconds (including the start-up time).
+    pub fn reset_blocking<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<(), Error<E>> {
+        self.start_reset()?;
+        delay.delay_us(RESET_START_UP_TIME_MS * 1000);
+        let mut elapsed_ms = RESET_START_UP_TIME_MS;
+        loop {
+            if self.has_reset_finished()? {
+                self.was_reset_started = false;
+                return Ok(());
+            }
+            if elapsed_ms >= timeout_ms {
+                return Err(Error::ResetTimeout);
+            }
+            delay.delay_us(RESET_POLL_INTERVAL_MS * 1000);
+            elapsed_ms += RESET_POLL_INTERVAL_MS;
+        }
+    }
+
+    fn start_reset(&mut self) -> Result<(), Error<E>> {
+        self.write_register(Register::CTRL2, BitFlags::SRST)?;
+        self.ctrl1 = Config::default();
+        self.ctrl2 = Config::default();
+        self.int_ctrl1 = Config {
+            bits: INT_CTRL1_DEFAULT,
+        };
+        self.data_ctrl = DATA_CTRL_DEFAULT;
+        self.int_ctrl2 = INT_CTRL2_DEFAULT;
+        self.wakeup_timer = WAKEUP_TIMER_DEFAULT;
+        self.wakeup_threshold = WAKEUP_THRESHOLD_DEFAULT;
+        self.was_reset_started = true;
+        Ok(())
+    }
+
     fn has_reset_finished(&mut self) -> Result<bool, Error<E>> {
         let ctrl2 = self.read_register(Register::CTRL2)?;
         Ok((ctrl2 & BitFlags::SRST) == 0)
     }
 
+    /// Check that the `WHO_AM_I` register value matches the device.
+    ///
+    /// Returns `Err(Error::WrongDevice)` including the value read otherwise.
+    pub fn verify_who_am_i(&mut self) -> Result<(), Error<E>> {
+        let who_am_i = self.who_am_i()?;
+        if IC::VARIANTS
+            .iter()
+            .any(|variant| variant.who_am_i() == who_am_i)
+        {
+            Ok(())
+        } else {
+            Err(Error::WrongDevice { who_am_i })
+        }
+    }
+
     /// Perform a digital communication self-test.
+    ///
+    /// On failure, `Err(Error::SelfTestError)` reports the stage at which
+    /// the test failed.
     pub fn communication_self_test(&mut self) -> Result<(), Error<E>> {
         let resp = self.read_register(Register::DCST_RESP)?;
         if resp != 0x55 {
-            return Err(Error::SelfTestError);
+            return Err(Error::SelfTestError(SelfTestStage::InitialResponse));
         }
         let ctrl2 = self.ctrl2.with_high(BitFlags::DCST);
         self.write_register(Register::CTRL2, ctrl2.bits)?;
         let resp = self.read_register(Register::DCST_RESP)?;
         if resp != 0xAA {
-            return Err(Error::SelfTestError);
+            return Err(Error::SelfTestError(SelfTestStage::TestResponse));
         }
         let ctrl2 = self.read_register(Register::CTRL2)?;
         if (ctrl2 & BitFlags::DCST) != 0 {
-            return Err(Error::SelfTestError);
+            return Err(Error::SelfTestError(SelfTestStage::DcstNotCleared));
         }
         let resp = self.read_register(Register::DCST_RESP)?;
         if resp != 0x55 {
-            return Err(Error::SelfTestError);
+            return Err(Error::SelfTestError(SelfTestStage::FinalResponse));
         }
         Ok(())
     }
@@ -480,6 +638,7 @@ where
         self.write_register(Register::SELF_TEST, 0)
     }
 
+    #[allow(clippy::manual_range_contains)]
     fn output_data_rate_greater_eq_400hz(&mut self) -> Result<bool, Error<E>> {
         let data_ctrl = self.read_register(Register::DATA_CTRL)?;
         Ok(data_ctrl >= 0b000_0101 && data_ctrl <= 0b000_0111)
@@ -495,6 +654,113 @@ impl<I2C, E, IC> Kxcj9<I2C, IC>
 where
     I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
 {
+    /// Compare the configuration registers of the device with the
+    /// configuration cached in the driver.
+    ///
+    /// A mismatch usually means that the device has been reset, for example
+    /// due to a brown-out. See [`restore_config()`](#method.restore_config).
+    pub fn verify_config(&mut self) -> Result<ConfigCheck, Error<E>> {
+        Ok(ConfigCheck {
+            ctrl1: self.read_register(Register::CTRL1)? == self.ctrl1.bits,
+            ctrl2: self.read_register(Register::CTRL2)? == self.ctrl2.bits,
+            data_ctrl: self.read_register(Register::DATA_CTRL)? == self.data_ctrl,
+            int_ctrl1: self.read_register(Register::INT_CTRL1)? == self.int_ctrl1.bits,
+            int_ctrl2: self.read_register(Register::INT_CTRL2)? == self.int_ctrl2,
+        })
+    }
+
+    /// Write the complete configuration cached in the driver to the device,
+    /// including the wake-up interrupt threshold and timer.
+    pub fn restore_config(&mut self) -> Result<(), Error<E>> {
+        let ctrl1 = self.ctrl1;
+        self.write_register(Register::CTRL1, ctrl1.with_low(BitFlags::PC1).bits)?;
+        self.write_register(Register::DATA_CTRL, self.data_ctrl)?;
+        self.write_register(Register::CTRL2, self.ctrl2.bits)?;
+        self.write_register(Register::INT_CTRL1, self.int_ctrl1.bits)?;
+        self.write_register(Register::INT_CTRL2, self.int_ctrl2)?;
+        self.write_register(Register::WAKEUP_TIMER, self.wakeup_timer)?;
+        self.write_register(Register::WAKEUP_THRESHOLD, self.wakeup_threshold)?;
+        self.write_register(Register::CTRL1, ctrl1.bits)
+    }
+
+    /// Check if any interrupt has happened.
+    pub fn has_interrupt_happened(&mut self) -> Result<bool, Error<E>> {
+        let status = self.read_register(Register::STATUS)?;
+        Ok((status & BitFlags::INT) != 0)
+    }
+
+    /// Read interrupt source information.
+    pub fn read_interrupt_info(&mut self) -> Result<InterruptInfo, Error<E>> {
+        let mut data = [0; 2];
+        self.i2c
+            .write_read(self.address, &[Register::INT_SOURCE1], &mut data)
+            .map_err(Error::I2C)?;
+        let info = InterruptInfo {
+            data_ready: is_high(data[0], BitFlags::DRDY),
+            wake_up: is_high(data[0], BitFlags::WUFS),
+            wake_up_x_positive: is_high(data[1], BitFlags::XPWU),
+            wake_up_x_negative: is_high(data[1], BitFlags::XNWU),
+            wake_up_y_positive: is_high(data[1], BitFlags::YPWU),
+            wake_up_y_negative: is_high(data[1], BitFlags::YNWU),
+            wake_up_z_positive: is_high(data[1], BitFlags::ZPWU),
+            wake_up_z_negative: is_high(data[1], BitFlags::ZNWU),
+        };
+        Ok(info)
+    }
+
+    /// Clear interrupts.
+    ///
+    /// This clears all interrupt source registers and changes the physical
+    /// interrupt pin to its inactive state.
+    pub fn clear_interrupts(&mut self) -> Result<(), Error<E>> {
+        self.read_register(Register::INT_REL).and(Ok(()))
+    }
+
+    pub(crate) fn interrupt_pin_polarity(&self) -> InterruptPinPolarity {
+        if self.int_ctrl1.is_high(BitFlags::IEA) {
+            InterruptPinPolarity::ActiveHigh
+        } else {
+            InterruptPinPolarity::ActiveLow
+        }
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn interrupt_pin_latching(&self) -> InterruptPinLatching {
+        if self.int_ctrl1.is_high(BitFlags::IEL) {
+            InterruptPinLatching::NonLatching
+        } else {
+            InterruptPinLatching::Latching
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.ctrl1.is_high(BitFlags::PC1)
+    }
+
+    pub(crate) fn wake_up_data_rate(&self) -> Option<WakeUpOutputDataRate> {
+        if self.ctrl1.is_high(BitFlags::WUFE) {
+            Some(WAKE_UP_DATA_RATES[usize::from(self.ctrl2.bits & 0b0000_0111)])
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn is_data_ready_interrupt_enabled(&self) -> bool {
+        self.ctrl1.is_high(BitFlags::DRDYE)
+    }
+
+    pub(crate) fn output_data_rate(&self) -> OutputDataRate {
+        OutputDataRate::from_data_ctrl(self.data_ctrl)
+    }
+
+    pub(crate) fn resolution(&self) -> Resolution {
+        if self.ctrl1.is_high(BitFlags::RES) {
+            Resolution::High
+        } else {
+            Resolution::Low
+        }
+    }
+
     fn update_ctrl1(&mut self, value: Config) -> Result<(), Error<E>> {
         self.write_register(Register::CTRL1, value.bits)?;
         self.ctrl1 = value;
@@ -516,18 +782,25 @@ where
     }
 }
 
-impl<I2C, E> Kxcj9<I2C, ic::G16Device>
+impl<I2C, E, IC> Kxcj9<I2C, IC>
 where
     I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    IC: ScaledDevice,
 {
     /// Set G scale.
-    pub fn set_scale(&mut self, scale: GScale16) -> Result<(), Error<E>> {
+    ///
+    /// The scale is a [`GScale8`] for KXCJ9-1008 and KXCJB-1041 devices and
+    /// a [`GScale16`] for KXCJ9-1018 devices.
+    ///
+    /// [`GScale8`]: enum.GScale8.html
+    /// [`GScale16`]: enum.GScale16.html
+    pub fn set_scale(&mut self, scale: IC::Scale) -> Result<(), Error<E>> {
         use self::BitFlags as BF;
-        let config = match scale {
-            GScale16::G4 => self.ctrl1.with_low(BF::GSEL0).with_low(BF::GSEL1),
-            GScale16::G8 => self.ctrl1.with_high(BF::GSEL0).with_low(BF::GSEL1),
-            GScale16::G16 => self.ctrl1.with_low(BF::GSEL0).with_high(BF::GSEL1),
-            GScale16::G16FP => self
+        let config = match IC::get_scale_config(scale) {
+            GScaleConfig::_0 => self.ctrl1.with_low(BF::GSEL0).with_low(BF::GSEL1),
+            GScaleConfig::_1 => self.ctrl1.with_high(BF::GSEL0).with_low(BF::GSEL1),
+            GScaleConfig::_2 => self.ctrl1.with_low(BF::GSEL0).with_high(BF::GSEL1),
+            GScaleConfig::_3 => self
                 .ctrl1
                 .with_high(BF::RES)
                 .with_high(BF::GSEL0)
@@ -536,27 +809,31 @@ where
         self.prepare_ctrl1_to_change_settings()?;
         self.update_ctrl1(config)
     }
-}
 
-impl<I2C, E> Kxcj9<I2C, ic::G8Device>
-where
-    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
-{
-    /// Set G scale.
-    pub fn set_scale(&mut self, scale: GScale8) -> Result<(), Error<E>> {
-        use self::BitFlags as BF;
-        let config = match scale {
-            GScale8::G2 => self.ctrl1.with_low(BF::GSEL0).with_low(BF::GSEL1),
-            GScale8::G4 => self.ctrl1.with_high(BF::GSEL0).with_low(BF::GSEL1),
-            GScale8::G8 => self.ctrl1.with_low(BF::GSEL0).with_high(BF::GSEL1),
-            GScale8::G8FP => self
-                .ctrl1
-                .with_high(BF::RES)
-                .with_high(BF::GSEL0)
-                .with_high(BF::GSEL1),
-        };
-        self.prepare_ctrl1_to_change_settings()?;
-        self.update_ctrl1(config)
+    /// Set the smallest G range covering the given acceleration in +/-g and
+    /// return the selected range.
+    ///
+    /// The full power scales are not selected.
+    /// Returns `Err(Error::InvalidSetting(SettingError::RangeOutOfRange))`
+    /// if the acceleration is larger than the largest range of the device.
+    pub fn set_range_at_least(&mut self, g: f32) -> Result<f32, Error<E>> {
+        let ranges = IC::CAPABILITIES.ranges_g;
+        let index = ranges
+            .iter()
+            .position(|&range| range >= g)
+            .ok_or(Error::InvalidSetting(SettingError::RangeOutOfRange))?;
+        self.set_scale(IC::SCALES[index])?;
+        Ok(ranges[index])
+    }
+
+    /// Configured G range in +/-g.
+    pub fn range_g(&self) -> f32 {
+        let ranges = IC::CAPABILITIES.ranges_g;
+        match GScaleConfig::from_ctrl1(self.ctrl1) {
+            GScaleConfig::_0 => ranges[0],
+            GScaleConfig::_1 => ranges[1],
+            GScaleConfig::_2 | GScaleConfig::_3 => ranges[2],
+        }
     }
 }
 
@@ -0,0 +1,181 @@
+use crate::{i2c, Error, Kxcj9, Measurement, PowerConfig, ScaledDevice, STANDBY_CURRENT_UA};
+use core::fmt;
+use hal::blocking::delay::DelayUs;
+
+/// Duty-cycled sampling configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DutyCycleConfig {
+    interval_ms: u32,
+    samples_per_wake: u16,
+}
+
+/// Invalid duty-cycled sampling configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DutyCycleConfigError {
+    /// The number of samples per wake-up is zero
+    ZeroSampleCount,
+}
+
+impl DutyCycleConfig {
+    /// Create a new configuration.
+    ///
+    /// `interval_ms` is the time between the start of two consecutive
+    /// wake-ups in milliseconds and `samples_per_wake` the number of
+    /// measurements taken and averaged per wake-up, which must be at least 1.
+    pub fn new(interval_ms: u32, samples_per_wake: u16) -> Result<Self, DutyCycleConfigError> {
+        if samples_per_wake == 0 {
+            return Err(DutyCycleConfigError::ZeroSampleCount);
+        }
+        Ok(DutyCycleConfig {
+            interval_ms,
+            samples_per_wake,
+        })
+    }
+
+    /// Time between the start of two consecutive wake-ups in milliseconds.
+    pub fn interval_ms(&self) -> u32 {
+        self.interval_ms
+    }
+
+    /// Number of measurements taken and averaged per wake-up.
+    pub fn samples_per_wake(&self) -> u16 {
+        self.samples_per_wake
+    }
+}
+
+impl Default for DutyCycleConfig {
+    fn default() -> Self {
+        DutyCycleConfig {
+            interval_ms: 1000,
+            samples_per_wake: 1,
+        }
+    }
+}
+
+/// Duty-cycled sampling scheduler
+///
+/// On each wake-up the device is enabled, the start-up time is awaited,
+/// the configured number of measurements is taken one measurement period
+/// apart and averaged, and the device is disabled again.
+///
+/// The output data rate and resolution configured in the driver are used.
+/// The scheduler does not own the driver.
+#[derive(Debug, Clone)]
+pub struct DutyCycleSampler {
+    config: DutyCycleConfig,
+}
+
+impl DutyCycleSampler {
+    /// Create a new duty-cycled sampler.
+    pub fn new(config: DutyCycleConfig) -> Self {
+        DutyCycleSampler { config }
+    }
+
+    /// Wake the device up, take the averaged measurement and put the
+    /// device back into standby.
+    pub fn sample<I2C, E, IC, D>(
+        &mut self,
+        sensor: &mut Kxcj9<I2C, IC>,
+        delay: &mut D,
+    ) -> Result<Measurement, Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+        D: DelayUs<u32>,
+    {
+        let count = self.config.samples_per_wake;
+        sensor.enable_and_wait(delay)?;
+        let period_us = sensor.output_data_rate().period_us();
+        let mut sum = Measurement::default();
+        for i in 0..count {
+            if i != 0 {
+                delay.delay_us(period_us);
+            }
+            match sensor.read() {
+                Ok(measurement) => sum = sum + measurement,
+                Err(e) => {
+                    let _ = sensor.disable();
+                    return Err(e);
+                }
+            }
+        }
+        sensor.disable()?;
+        Ok(sum / f32::from(count))
+    }
+
+    /// Take the averaged measurement and wait for the rest of the interval.
+    ///
+    /// See [`sample()`](#method.sample).
+    pub fn sample_and_wait<I2C, E, IC, D>(
+        &mut self,
+        sensor: &mut Kxcj9<I2C, IC>,
+        delay: &mut D,
+    ) -> Result<Measurement, Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+        D: DelayUs<u32>,
+    {
+        let measurement = self.sample(sensor, delay)?;
+        let interval_us = self.config.interval_ms.saturating_mul(1000);
+        let remaining_us = interval_us.saturating_sub(self.active_time_us(sensor));
+        if remaining_us > 0 {
+            delay.delay_us(remaining_us);
+        }
+        Ok(measurement)
+    }
+
+    /// Time the device is enabled per wake-up in microseconds for the
+    /// current output data rate and resolution.
+    ///
+    /// This does not include the time spent on I²C communication.
+    pub fn active_time_us<I2C, E, IC>(&self, sensor: &Kxcj9<I2C, IC>) -> u32
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+    {
+        let extra_samples = u32::from(self.config.samples_per_wake.saturating_sub(1));
+        sensor.start_up_time_us() + extra_samples * sensor.output_data_rate().period_us()
+    }
+
+    /// Effective duty cycle (fraction of time the device is enabled) in
+    /// the range `[0.0-1.0]` for the current output data rate and resolution.
+    pub fn duty_cycle<I2C, E, IC>(&self, sensor: &Kxcj9<I2C, IC>) -> f32
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+    {
+        let interval_us = self.config.interval_ms as f32 * 1000.0;
+        (self.active_time_us(sensor) as f32 / interval_us).min(1.0)
+    }
+
+    /// Estimated average typical supply current in µA for the current
+    /// output data rate and resolution taking the duty cycle into account.
+    ///
+    /// See [`PowerConfig::typical_current_ua()`](struct.PowerConfig.html#method.typical_current_ua).
+    pub fn average_current_ua<I2C, E, IC>(&self, sensor: &Kxcj9<I2C, IC>) -> f32
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+    {
+        let active = PowerConfig {
+            enabled: true,
+            ..sensor.power_config()
+        };
+        let duty_cycle = self.duty_cycle(sensor);
+        duty_cycle * active.typical_current_ua() + (1.0 - duty_cycle) * STANDBY_CURRENT_UA
+    }
+}
+
+impl fmt::Display for DutyCycleConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DutyCycleConfigError::ZeroSampleCount => {
+                write!(f, "number of samples must be at least 1")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DutyCycleConfigError {}
@@ -0,0 +1,189 @@
+use crate::{i2c, Axis, Direction, Error, Event, InterruptInfo, Kxcj9, WakeUpTriggerMotion};
+
+const MOTIONS: [(Axis, Direction); 6] = [
+    (Axis::X, Direction::Negative),
+    (Axis::X, Direction::Positive),
+    (Axis::Y, Direction::Negative),
+    (Axis::Y, Direction::Positive),
+    (Axis::Z, Direction::Negative),
+    (Axis::Z, Direction::Positive),
+];
+
+/// Iterator over the events contained in the interrupt source information
+///
+/// The data ready event is returned first, followed by the motion events
+/// in the order X, Y, Z with the negative direction first.
+#[derive(Debug, Clone)]
+pub struct Events {
+    info: InterruptInfo,
+    index: usize,
+}
+
+impl Iterator for Events {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        while self.index <= MOTIONS.len() {
+            let index = self.index;
+            self.index += 1;
+            if index == 0 {
+                if self.info.data_ready {
+                    return Some(Event::DataReady);
+                }
+            } else {
+                let (axis, direction) = MOTIONS[index - 1];
+                if self.info.has_motion(axis, direction) {
+                    return Some(Event::Motion { axis, direction });
+                }
+            }
+        }
+        None
+    }
+}
+
+impl InterruptInfo {
+    /// Iterate over the events contained in this interrupt source information.
+    pub fn events(&self) -> Events {
+        Events {
+            info: self.clone(),
+            index: 0,
+        }
+    }
+
+    /// Check if wake-up motion was detected along an axis in a direction.
+    pub fn has_motion(&self, axis: Axis, direction: Direction) -> bool {
+        match (axis, direction) {
+            (Axis::X, Direction::Negative) => self.wake_up_x_negative,
+            (Axis::X, Direction::Positive) => self.wake_up_x_positive,
+            (Axis::Y, Direction::Negative) => self.wake_up_y_negative,
+            (Axis::Y, Direction::Positive) => self.wake_up_y_positive,
+            (Axis::Z, Direction::Negative) => self.wake_up_z_negative,
+            (Axis::Z, Direction::Positive) => self.wake_up_z_positive,
+        }
+    }
+}
+
+impl WakeUpTriggerMotion {
+    /// Trigger motion with all axes and directions disabled.
+    pub fn none() -> Self {
+        WakeUpTriggerMotion {
+            x_negative: false,
+            x_positive: false,
+            y_negative: false,
+            y_positive: false,
+            z_negative: false,
+            z_positive: false,
+        }
+    }
+
+    /// Enable the wake-up interrupt on motion along an axis in a direction.
+    pub fn with(mut self, axis: Axis, direction: Direction) -> Self {
+        *self.get_mut(axis, direction) = true;
+        self
+    }
+
+    /// Disable the wake-up interrupt on motion along an axis in a direction.
+    pub fn without(mut self, axis: Axis, direction: Direction) -> Self {
+        *self.get_mut(axis, direction) = false;
+        self
+    }
+
+    /// Check if the wake-up interrupt is enabled on motion along an axis
+    /// in a direction.
+    pub fn is_enabled(&self, axis: Axis, direction: Direction) -> bool {
+        match (axis, direction) {
+            (Axis::X, Direction::Negative) => self.x_negative,
+            (Axis::X, Direction::Positive) => self.x_positive,
+            (Axis::Y, Direction::Negative) => self.y_negative,
+            (Axis::Y, Direction::Positive) => self.y_positive,
+            (Axis::Z, Direction::Negative) => self.z_negative,
+            (Axis::Z, Direction::Positive) => self.z_positive,
+        }
+    }
+
+    fn get_mut(&mut self, axis: Axis, direction: Direction) -> &mut bool {
+        match (axis, direction) {
+            (Axis::X, Direction::Negative) => &mut self.x_negative,
+            (Axis::X, Direction::Positive) => &mut self.x_positive,
+            (Axis::Y, Direction::Negative) => &mut self.y_negative,
+            (Axis::Y, Direction::Positive) => &mut self.y_positive,
+            (Axis::Z, Direction::Negative) => &mut self.z_negative,
+            (Axis::Z, Direction::Positive) => &mut self.z_positive,
+        }
+    }
+}
+
+impl<I2C, E, IC> Kxcj9<I2C, IC>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+{
+    /// Read the interrupt sources and return the events that happened.
+    ///
+    /// Both interrupt source registers are read in a single transaction.
+    /// The interrupts stay latched. See [`poll_events_and_clear()`](#method.poll_events_and_clear).
+    pub fn poll_events(&mut self) -> Result<Events, Error<E>> {
+        Ok(self.read_interrupt_info()?.events())
+    }
+
+    /// Read the interrupt sources, return the events that happened and
+    /// clear the interrupts afterwards.
+    pub fn poll_events_and_clear(&mut self) -> Result<Events, Error<E>> {
+        let events = self.poll_events()?;
+        self.clear_interrupts()?;
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_events() {
+        assert_eq!(None, InterruptInfo::default().events().next());
+    }
+
+    #[test]
+    fn can_iterate_events_in_order() {
+        let info = InterruptInfo {
+            data_ready: true,
+            wake_up: true,
+            wake_up_x_positive: true,
+            wake_up_z_negative: true,
+            ..Default::default()
+        };
+        let mut events = info.events();
+        assert_eq!(Some(Event::DataReady), events.next());
+        assert_eq!(
+            Some(Event::Motion {
+                axis: Axis::X,
+                direction: Direction::Positive
+            }),
+            events.next()
+        );
+        assert_eq!(
+            Some(Event::Motion {
+                axis: Axis::Z,
+                direction: Direction::Negative
+            }),
+            events.next()
+        );
+        assert_eq!(None, events.next());
+        assert_eq!(None, events.next());
+    }
+
+    #[test]
+    fn can_build_trigger_motion() {
+        let motion = WakeUpTriggerMotion::none()
+            .with(Axis::Y, Direction::Negative)
+            .with(Axis::Z, Direction::Positive);
+        for &(axis, direction) in MOTIONS.iter() {
+            let expected = (axis, direction) == (Axis::Y, Direction::Negative)
+                || (axis, direction) == (Axis::Z, Direction::Positive);
+            assert_eq!(expected, motion.is_enabled(axis, direction));
+        }
+        let motion = WakeUpTriggerMotion::default().without(Axis::X, Direction::Positive);
+        assert!(!motion.x_positive);
+        assert!(motion.x_negative);
+    }
+}
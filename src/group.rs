@@ -0,0 +1,120 @@
+use crate::bus::{BorrowedBus, Detached};
+use crate::{i2c, ic, Error, Kxcj9, Measurement, ScaledDevice, SlaveAddr, UnscaledMeasurement};
+
+/// Group of devices of the same variant sharing one I²C bus
+///
+/// The group owns the bus and a detached driver for each device address.
+/// Operations are applied to every device in turn and the result of each
+/// device is reported separately, so that an error in one device does not
+/// prevent the operation on the others.
+///
+/// Up to two devices can be connected to one bus using
+/// `SlaveAddr::Alternative(false)` and `SlaveAddr::Alternative(true)`.
+#[derive(Debug)]
+pub struct SensorGroup<I2C, IC, const N: usize> {
+    i2c: I2C,
+    sensors: [Kxcj9<Detached, IC>; N],
+}
+
+impl<I2C, E, const N: usize> SensorGroup<I2C, ic::G8Device, N>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+{
+    /// Create a new group of KXCJ9-1008 devices.
+    pub fn new_kxcj9_1008(i2c: I2C, addresses: [SlaveAddr; N]) -> Self {
+        Self::create(i2c, addresses)
+    }
+
+    /// Create a new group of KXCJB-1041 devices.
+    pub fn new_kxcjb_1041(i2c: I2C, addresses: [SlaveAddr; N]) -> Self {
+        Self::create(i2c, addresses)
+    }
+}
+
+impl<I2C, E, const N: usize> SensorGroup<I2C, ic::G16Device, N>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+{
+    /// Create a new group of KXCJ9-1018 devices.
+    pub fn new_kxcj9_1018(i2c: I2C, addresses: [SlaveAddr; N]) -> Self {
+        Self::create(i2c, addresses)
+    }
+}
+
+impl<I2C, E, IC, const N: usize> SensorGroup<I2C, IC, N>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    IC: ScaledDevice,
+{
+    fn create(i2c: I2C, addresses: [SlaveAddr; N]) -> Self {
+        SensorGroup {
+            i2c,
+            sensors: addresses.map(|address| Kxcj9::create(Detached(()), address)),
+        }
+    }
+
+    /// Destroy the group, return the I²C bus instance.
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+
+    /// Number of devices in the group.
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    /// Whether the group contains no devices.
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Run an operation on every device in turn and return the result
+    /// of each device.
+    ///
+    /// This can be used to apply the same configuration to all devices.
+    pub fn for_each<F, R>(&mut self, mut f: F) -> [R; N]
+    where
+        F: FnMut(&mut Kxcj9<BorrowedBus<'_, I2C>, IC>) -> R,
+    {
+        let i2c = &mut self.i2c;
+        let mut sensors = self.sensors.iter_mut();
+        [(); N].map(|_| {
+            // The iterator yields exactly N elements.
+            let sensor = sensors.next().unwrap();
+            sensor.with_bus(i2c, &mut f)
+        })
+    }
+
+    /// Run an operation on a single device.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn with_sensor<F, R>(&mut self, index: usize, f: F) -> R
+    where
+        F: FnOnce(&mut Kxcj9<BorrowedBus<'_, I2C>, IC>) -> R,
+    {
+        self.sensors[index].with_bus(&mut self.i2c, f)
+    }
+
+    /// Enable all devices.
+    pub fn enable(&mut self) -> [Result<(), Error<E>>; N] {
+        self.for_each(|sensor| sensor.enable())
+    }
+
+    /// Disable all devices.
+    pub fn disable(&mut self) -> [Result<(), Error<E>>; N] {
+        self.for_each(|sensor| sensor.disable())
+    }
+
+    /// Read the acceleration of all devices back-to-back scaled to the
+    /// configured G range of each device.
+    pub fn read(&mut self) -> [Result<Measurement, Error<E>>; N] {
+        self.for_each(|sensor| sensor.read())
+    }
+
+    /// Read the unscaled acceleration of all devices back-to-back.
+    pub fn read_unscaled(&mut self) -> [Result<UnscaledMeasurement, Error<E>>; N] {
+        self.for_each(|sensor| sensor.read_unscaled())
+    }
+}
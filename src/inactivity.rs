@@ -0,0 +1,216 @@
+use crate::Measurement;
+use libm::fabsf;
+
+/// How measurements are compared against the inactivity threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotionComparison {
+    /// The change along each axis is compared separately (default)
+    PerAxis,
+    /// The magnitude of the change vector is compared
+    Magnitude,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for MotionComparison {
+    fn default() -> Self {
+        MotionComparison::PerAxis
+    }
+}
+
+/// Event reported by the [`InactivityDetector`](struct.InactivityDetector.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InactivityEvent {
+    /// No motion detected for the configured duration
+    NoMotion,
+}
+
+/// Inactivity detection configuration
+#[derive(Debug, Clone, Copy)]
+pub struct InactivityConfig {
+    /// Maximum acceleration change in G still considered as no motion.
+    pub threshold: f32,
+    /// How the acceleration change is compared to the threshold.
+    pub comparison: MotionComparison,
+    /// Time without motion necessary to report inactivity in milliseconds.
+    pub duration_ms: u32,
+}
+
+impl Default for InactivityConfig {
+    fn default() -> Self {
+        InactivityConfig {
+            threshold: 0.1,
+            comparison: MotionComparison::default(),
+            duration_ms: 10_000,
+        }
+    }
+}
+
+/// Software no-motion detector
+///
+/// Each measurement is compared against a reference measurement. If the
+/// change exceeds the threshold, motion is considered to have happened and
+/// the measurement becomes the new reference. Once no motion has been seen
+/// for the configured duration, an `InactivityEvent::NoMotion` is returned. This
+/// happens only once per inactivity period.
+///
+/// This complements the hardware wake-up interrupt, which only reports
+/// the start of motion.
+#[derive(Debug, Clone)]
+pub struct InactivityDetector {
+    config: InactivityConfig,
+    reference: Option<Measurement>,
+    still_ms: u32,
+    inactive: bool,
+}
+
+impl InactivityDetector {
+    /// Create a new inactivity detector.
+    pub fn new(config: InactivityConfig) -> Self {
+        InactivityDetector {
+            config,
+            reference: None,
+            still_ms: 0,
+            inactive: false,
+        }
+    }
+
+    /// Feed a new measurement in G.
+    ///
+    /// `elapsed_ms` is the time elapsed since the previous measurement.
+    /// Returns `InactivityEvent::NoMotion` when the device has just become inactive.
+    pub fn update(&mut self, measurement: Measurement, elapsed_ms: u32) -> Option<InactivityEvent> {
+        let reference = match self.reference {
+            Some(reference) => reference,
+            None => {
+                self.reference = Some(measurement);
+                return None;
+            }
+        };
+        if self.is_motion(measurement - reference) {
+            self.reference = Some(measurement);
+            self.still_ms = 0;
+            self.inactive = false;
+            return None;
+        }
+        self.still_ms = self.still_ms.saturating_add(elapsed_ms);
+        if !self.inactive && self.still_ms >= self.config.duration_ms {
+            self.inactive = true;
+            Some(InactivityEvent::NoMotion)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the device is currently considered inactive.
+    pub fn is_inactive(&self) -> bool {
+        self.inactive
+    }
+
+    /// Time elapsed without motion in milliseconds.
+    pub fn still_ms(&self) -> u32 {
+        self.still_ms
+    }
+
+    /// Forget the reference measurement and the elapsed time.
+    pub fn reset(&mut self) {
+        self.reference = None;
+        self.still_ms = 0;
+        self.inactive = false;
+    }
+
+    fn is_motion(&self, change: Measurement) -> bool {
+        let threshold = self.config.threshold;
+        match self.config.comparison {
+            MotionComparison::PerAxis => {
+                fabsf(change.x) > threshold
+                    || fabsf(change.y) > threshold
+                    || fabsf(change.z) > threshold
+            }
+            MotionComparison::Magnitude => change.magnitude() > threshold,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(x: f32, y: f32, z: f32) -> Measurement {
+        Measurement { x, y, z }
+    }
+
+    fn new(comparison: MotionComparison) -> InactivityDetector {
+        InactivityDetector::new(InactivityConfig {
+            threshold: 0.1,
+            comparison,
+            duration_ms: 100,
+        })
+    }
+
+    #[test]
+    fn reports_no_motion_once() {
+        let mut detector = new(MotionComparison::PerAxis);
+        assert_eq!(None, detector.update(m(0.0, 0.0, 1.0), 50));
+        assert_eq!(None, detector.update(m(0.05, 0.0, 1.0), 50));
+        assert!(!detector.is_inactive());
+        assert_eq!(
+            Some(InactivityEvent::NoMotion),
+            detector.update(m(0.0, 0.05, 1.0), 50)
+        );
+        assert!(detector.is_inactive());
+        assert_eq!(None, detector.update(m(0.0, 0.0, 1.0), 50));
+    }
+
+    #[test]
+    fn motion_restarts_duration() {
+        let mut detector = new(MotionComparison::PerAxis);
+        detector.update(m(0.0, 0.0, 1.0), 50);
+        detector.update(m(0.0, 0.0, 1.0), 90);
+        assert_eq!(None, detector.update(m(0.0, 0.0, 1.2), 50));
+        assert_eq!(0, detector.still_ms());
+        assert_eq!(None, detector.update(m(0.0, 0.0, 1.2), 90));
+        assert_eq!(
+            Some(InactivityEvent::NoMotion),
+            detector.update(m(0.0, 0.0, 1.2), 10)
+        );
+    }
+
+    #[test]
+    fn motion_after_inactivity_rearms() {
+        let mut detector = new(MotionComparison::PerAxis);
+        detector.update(m(0.0, 0.0, 1.0), 0);
+        assert_eq!(
+            Some(InactivityEvent::NoMotion),
+            detector.update(m(0.0, 0.0, 1.0), 100)
+        );
+        detector.update(m(0.5, 0.0, 1.0), 10);
+        assert!(!detector.is_inactive());
+        assert_eq!(
+            Some(InactivityEvent::NoMotion),
+            detector.update(m(0.5, 0.0, 1.0), 100)
+        );
+    }
+
+    #[test]
+    fn magnitude_comparison_combines_axes() {
+        let mut per_axis = new(MotionComparison::PerAxis);
+        let mut magnitude = new(MotionComparison::Magnitude);
+        for detector in [&mut per_axis, &mut magnitude].iter_mut() {
+            detector.update(m(0.0, 0.0, 1.0), 0);
+            detector.update(m(0.08, 0.08, 1.0), 50);
+        }
+        assert_eq!(50, per_axis.still_ms());
+        assert_eq!(0, magnitude.still_ms());
+    }
+
+    #[test]
+    fn can_reset() {
+        let mut detector = new(MotionComparison::PerAxis);
+        detector.update(m(0.0, 0.0, 1.0), 0);
+        detector.update(m(0.0, 0.0, 1.0), 100);
+        detector.reset();
+        assert!(!detector.is_inactive());
+        assert_eq!(None, detector.update(m(1.0, 0.0, 0.0), 100));
+        assert_eq!(0, detector.still_ms());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::hal::digital::v2::InputPin;
+use crate::{i2c, nb, Error, InterruptInfo, InterruptPinPolarity, Kxcj9, ScaledDevice};
+
+/// Errors when using the physical interrupt pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptPinError<E, PE> {
+    /// Device error
+    Device(Error<E>),
+    /// Interrupt pin error
+    Pin(PE),
+}
+
+/// KXCJ9/KXCJB device driver together with the physical interrupt pin
+///
+/// The pin level is interpreted according to the interrupt pin polarity
+/// configured through the driver. See [`set_interrupt_pin_polarity()`].
+///
+/// [`set_interrupt_pin_polarity()`]: struct.Kxcj9.html#method.set_interrupt_pin_polarity
+#[derive(Debug)]
+pub struct WithInterruptPin<I2C, IC, PIN> {
+    sensor: Kxcj9<I2C, IC>,
+    pin: PIN,
+}
+
+impl<I2C, E, IC> Kxcj9<I2C, IC>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+{
+    /// Combine the driver with the input pin connected to the physical
+    /// interrupt pin of the device.
+    ///
+    /// The physical interrupt pin still needs to be enabled.
+    /// See [`enable_interrupt_pin()`](#method.enable_interrupt_pin).
+    pub fn with_interrupt_pin<PIN>(self, pin: PIN) -> WithInterruptPin<I2C, IC, PIN> {
+        WithInterruptPin { sensor: self, pin }
+    }
+}
+
+impl<I2C, E, IC, PIN> WithInterruptPin<I2C, IC, PIN>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    IC: ScaledDevice,
+{
+    /// Destroy the wrapper, returning the driver and the interrupt pin.
+    pub fn destroy(self) -> (Kxcj9<I2C, IC>, PIN) {
+        (self.sensor, self.pin)
+    }
+
+    /// Access the driver.
+    pub fn sensor(&mut self) -> &mut Kxcj9<I2C, IC> {
+        &mut self.sensor
+    }
+
+    /// Read the interrupt source information and clear the interrupts.
+    fn read_and_clear<PE>(&mut self) -> Result<InterruptInfo, InterruptPinError<E, PE>> {
+        let info = self
+            .sensor
+            .read_interrupt_info()
+            .map_err(InterruptPinError::Device)?;
+        self.sensor
+            .clear_interrupts()
+            .map_err(InterruptPinError::Device)?;
+        Ok(info)
+    }
+}
+
+impl<I2C, E, IC, PIN, PE> WithInterruptPin<I2C, IC, PIN>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    IC: ScaledDevice,
+    PIN: InputPin<Error = PE>,
+{
+    /// Check if the interrupt pin is in its active state.
+    pub fn is_interrupt_active(&self) -> Result<bool, PE> {
+        match self.sensor.interrupt_pin_polarity() {
+            InterruptPinPolarity::ActiveHigh => self.pin.is_high(),
+            InterruptPinPolarity::ActiveLow => self.pin.is_low(),
+        }
+    }
+
+    /// Wait for the interrupt pin to become active and return the decoded
+    /// interrupt source information.
+    ///
+    /// This method offers a non-blocking interface. While the interrupt
+    /// pin is inactive this will return `nb::Error::WouldBlock`.
+    /// Otherwise the interrupt source information is read and the
+    /// interrupts are cleared.
+    ///
+    /// Since the pin level is polled, latching interrupt pin behavior
+    /// should be used. See [`set_interrupt_pin_latching()`].
+    ///
+    /// [`set_interrupt_pin_latching()`]: struct.Kxcj9.html#method.set_interrupt_pin_latching
+    pub fn wait_for_interrupt(&mut self) -> nb::Result<InterruptInfo, InterruptPinError<E, PE>> {
+        if !self.is_interrupt_active().map_err(InterruptPinError::Pin)? {
+            return Err(nb::Error::WouldBlock);
+        }
+        Ok(self.read_and_clear()?)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I2C, E, IC, PIN> WithInterruptPin<I2C, IC, PIN>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    IC: ScaledDevice,
+    PIN: embedded_hal_async::digital::Wait,
+{
+    /// Asynchronously wait for an interrupt and return the decoded
+    /// interrupt source information.
+    ///
+    /// With latching interrupt pin behavior this waits for the active
+    /// level. Otherwise it waits for the edge of the interrupt pulse.
+    /// Afterwards the interrupt source information is read and the
+    /// interrupts are cleared.
+    pub async fn wait_for_interrupt_async(
+        &mut self,
+    ) -> Result<InterruptInfo, InterruptPinError<E, PIN::Error>> {
+        let latching =
+            self.sensor.interrupt_pin_latching() == crate::InterruptPinLatching::Latching;
+        let result = match (self.sensor.interrupt_pin_polarity(), latching) {
+            (InterruptPinPolarity::ActiveHigh, true) => self.pin.wait_for_high().await,
+            (InterruptPinPolarity::ActiveLow, true) => self.pin.wait_for_low().await,
+            (InterruptPinPolarity::ActiveHigh, false) => self.pin.wait_for_rising_edge().await,
+            (InterruptPinPolarity::ActiveLow, false) => self.pin.wait_for_falling_edge().await,
+        };
+        result.map_err(InterruptPinError::Pin)?;
+        self.read_and_clear()
+    }
+}
@@ -5,43 +5,132 @@
 //!
 //! This driver allows you to:
 //! - Enable/disable the device. See [`enable()`].
+//! - Enable the device and wait until the first valid measurement is available.
+//!   See [`enable_and_wait()`].
 //! - Read the acceleration measurement. See [`read()`].
 //! - Read the unscaled acceleration measurement. See [`read_unscaled()`].
+//! - Read only new acceleration measurements. See [`read_new()`] and [`read_new_blocking()`].
+//! - Read the acceleration measurement in SI units (m/s²). See [`read_m_s2()`].
+//! - Set the standard gravity used for SI unit conversion. See [`set_standard_gravity()`].
+//! - Read the acceleration measurement as typed [`uom`] quantities (requires
+//!   the `uom` feature). See [`read_acceleration()`].
+//! - Vector operations on measurements (addition, subtraction, scaling,
+//!   magnitude, normalization, dot product and angle between measurements).
+//!   See [`Measurement`] and [`UnscaledMeasurement`].
+//! - Conversions into [`mint`], [`nalgebra`] and [`micromath`] vector types
+//!   (requires the feature with the same name).
 //! - Set resolution. See [`set_resolution()`].
 //! - Set output data rate. See [`set_output_data_rate()`].
 //! - Set +/- G range. See [`set_scale()`].
+//! - Set the smallest +/- G range covering an acceleration independently of
+//!   the device variant. See [`set_range_at_least()`].
 //! - Read `WHO_AM_I` register. See [`who_am_i()`].
-//! - Perform a software reset. See [`reset()`].
+//! - Check that the `WHO_AM_I` register matches the device. See [`verify_who_am_i()`].
+//! - Perform a software reset. See [`reset()`] and [`reset_blocking()`].
 //! - Run a communication self-test. See [`communication_self_test()`].
 //! - Enable/disable MEMS self-test function. See [`enable_mems_self_test()`].
 //! - Interrupt support:
 //!     - Enable/disable new acceleration data ready interrupt. See [`enable_data_ready_interrupt()`].
 //!     - Enable/disable and configure wake-up motion detected interrupt. See [`enable_wake_up_interrupt()`].
+//!     - Configure the wake-up motion delay in milliseconds. See [`WakeUpInterruptConfig::from_delay_ms()`].
 //!     - Enable/disable physical interrupt pin. See [`enable_interrupt_pin()`].
 //!     - Set physical interrupt pin polarity. See [`set_interrupt_pin_polarity()`].
 //!     - Set physical interrupt pin latching behavior. See [`set_interrupt_pin_latching()`].
 //!     - Check if any interrupt has happened. See [`has_interrupt_happened()`].
 //!     - Clear interrupts. See [`clear_interrupts()`].
 //!     - Read interrupt source information. See [`read_interrupt_info()`].
+//!     - Iterate over the interrupt events that happened. See [`poll_events()`].
+//!     - Wait for the physical interrupt pin to become active using an input
+//!       pin (also asynchronously with the `async` feature). See [`with_interrupt_pin()`].
+//! - Wake-on-motion sleep/active power state machine. See [`WakeOnMotion`].
+//! - Software no-motion (inactivity) detection. See [`InactivityDetector`].
+//! - Capture a burst of high-rate samples on wake-up motion. See [`BurstCapture`].
+//! - Oscilloscope-style capture of samples before and after a trigger.
+//!   See [`CaptureBuffer`].
+//! - Estimate the typical supply current and the I²C bus activity.
+//!   See [`typical_current_ua()`] and [`BusActivity`].
+//! - Duty-cycled sampling with averaging. See [`DutyCycleSampler`].
+//! - Share the I²C bus with other devices through `RefCell`, critical section
+//!   (requires the `critical-section` feature) or `std::sync::Mutex` (requires
+//!   the `std` feature) proxies, or borrow the bus for each call.
+//!   See the [`bus`] module and [`detach()`].
+//! - Drive several devices on one bus with the same configuration and read
+//!   them back-to-back. See [`SensorGroup`].
+//! - Scan the I²C bus for devices and identify their variant. See [`probe()`].
+//! - Detect unexpected device resets and restore the configuration.
+//!   See [`verify_config()`], [`restore_config()`] and [`Supervisor`].
+//! - Retry I²C transactions after transient errors with retry counters.
+//!   See [`RetryingI2c`].
+//! - Trace every register access with a hook. Ready-made hooks log with
+//!   [`log`] or [`defmt`] (requires the feature with the same name) or record
+//!   in memory. See [`with_trace_hook()`] and [`TraceHook`].
+//! - Record the I²C traffic in a text format and replay it later.
+//!   See [`TextRecorder`] and [`ReplayI2c`].
+//! - Query the ranges, resolutions, output data rates and wake-up threshold
+//!   limits of each device variant. See [`Capabilities`] and [`capabilities()`].
+//! - Use the device variant detected or selected at runtime without generics.
+//!   See [`AnyKxcj9`].
 //!
 //! [`enable()`]: struct.Kxcj9.html#method.enable
+//! [`enable_and_wait()`]: struct.Kxcj9.html#method.enable_and_wait
 //! [`read()`]: struct.Kxcj9.html#method.read
 //! [`read_unscaled()`]: struct.Kxcj9.html#method.read_unscaled
+//! [`read_new()`]: struct.Kxcj9.html#method.read_new
+//! [`read_new_blocking()`]: struct.Kxcj9.html#method.read_new_blocking
+//! [`read_m_s2()`]: struct.Kxcj9.html#method.read_m_s2
+//! [`set_standard_gravity()`]: struct.Kxcj9.html#method.set_standard_gravity
+//! [`uom`]: https://crates.io/crates/uom
+//! [`read_acceleration()`]: struct.Kxcj9.html#method.read_acceleration
+//! [`Measurement`]: struct.Measurement.html
+//! [`UnscaledMeasurement`]: struct.UnscaledMeasurement.html
+//! [`mint`]: https://crates.io/crates/mint
+//! [`nalgebra`]: https://crates.io/crates/nalgebra
+//! [`micromath`]: https://crates.io/crates/micromath
 //! [`set_resolution()`]: struct.Kxcj9.html#method.set_resolution
 //! [`set_output_data_rate()`]: struct.Kxcj9.html#method.set_output_data_rate
 //! [`set_scale()`]: struct.Kxcj9.html#method.set_scale
+//! [`set_range_at_least()`]: struct.Kxcj9.html#method.set_range_at_least
 //! [`who_am_i()`]: struct.Kxcj9.html#method.who_am_i
+//! [`verify_who_am_i()`]: struct.Kxcj9.html#method.verify_who_am_i
 //! [`reset()`]: struct.Kxcj9.html#method.reset
+//! [`reset_blocking()`]: struct.Kxcj9.html#method.reset_blocking
 //! [`communication_self_test()`]: struct.Kxcj9.html#method.communication_self_test
 //! [`enable_mems_self_test()`]: struct.Kxcj9.html#method.enable_mems_self_test
 //! [`enable_data_ready_interrupt()`]: struct.Kxcj9.html#method.enable_data_ready_interrupt
 //! [`enable_wake_up_interrupt()`]: struct.Kxcj9.html#method.enable_wake_up_interrupt
+//! [`WakeUpInterruptConfig::from_delay_ms()`]: struct.WakeUpInterruptConfig.html#method.from_delay_ms
 //! [`enable_interrupt_pin()`]: struct.Kxcj9.html#method.enable_interrupt_pin
 //! [`set_interrupt_pin_polarity()`]: struct.Kxcj9.html#method.set_interrupt_pin_polarity
 //! [`set_interrupt_pin_latching()`]: struct.Kxcj9.html#method.set_interrupt_pin_latching
 //! [`has_interrupt_happened()`]: struct.Kxcj9.html#method.has_interrupt_happened
 //! [`clear_interrupts()`]: struct.Kxcj9.html#method.clear_interrupts
 //! [`read_interrupt_info()`]: struct.Kxcj9.html#method.read_interrupt_info
+//! [`poll_events()`]: struct.Kxcj9.html#method.poll_events
+//! [`with_interrupt_pin()`]: struct.Kxcj9.html#method.with_interrupt_pin
+//! [`WakeOnMotion`]: struct.WakeOnMotion.html
+//! [`InactivityDetector`]: struct.InactivityDetector.html
+//! [`BurstCapture`]: struct.BurstCapture.html
+//! [`CaptureBuffer`]: struct.CaptureBuffer.html
+//! [`typical_current_ua()`]: struct.Kxcj9.html#method.typical_current_ua
+//! [`BusActivity`]: struct.BusActivity.html
+//! [`DutyCycleSampler`]: struct.DutyCycleSampler.html
+//! [`bus`]: bus/index.html
+//! [`detach()`]: struct.Kxcj9.html#method.detach
+//! [`SensorGroup`]: struct.SensorGroup.html
+//! [`probe()`]: fn.probe.html
+//! [`verify_config()`]: struct.Kxcj9.html#method.verify_config
+//! [`restore_config()`]: struct.Kxcj9.html#method.restore_config
+//! [`Supervisor`]: struct.Supervisor.html
+//! [`RetryingI2c`]: struct.RetryingI2c.html
+//! [`log`]: https://crates.io/crates/log
+//! [`defmt`]: https://crates.io/crates/defmt
+//! [`with_trace_hook()`]: struct.Kxcj9.html#method.with_trace_hook
+//! [`TraceHook`]: trait.TraceHook.html
+//! [`TextRecorder`]: struct.TextRecorder.html
+//! [`ReplayI2c`]: struct.ReplayI2c.html
+//! [`Capabilities`]: struct.Capabilities.html
+//! [`capabilities()`]: struct.Kxcj9.html#method.capabilities
+//! [`AnyKxcj9`]: struct.AnyKxcj9.html
 //!
 //! [Introductory blog post](https://blog.eldruin.com/kxcj9-kxcjb-tri-axis-mems-accelerator-driver-in-rust/)
 //!
@@ -101,6 +190,81 @@
 //! # }
 //! ```
 //!
+//! ### Read acceleration in m/s²
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! use kxcj9::{Kxcj9, SlaveAddr};
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Kxcj9::new_kxcj9_1018(dev, SlaveAddr::default());
+//! // Optionally use the local gravity instead of the standard value
+//! sensor.set_standard_gravity(9.81);
+//! sensor.enable().unwrap();
+//! let acc = sensor.read_m_s2().unwrap();
+//! println!("X: {:2}, Y: {:2}, Z: {:2} m/s²", acc.x, acc.y, acc.z);
+//! # }
+//! ```
+//!
+//! ### Calculate the tilt angle relative to the resting position
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! use kxcj9::{Kxcj9, SlaveAddr};
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Kxcj9::new_kxcj9_1018(dev, SlaveAddr::default());
+//! sensor.enable().unwrap();
+//! let rest = sensor.read().unwrap();
+//! let acc = sensor.read().unwrap();
+//! let angle = acc.angle_between(&rest);
+//! let shock = (acc - rest).magnitude();
+//! println!("Tilt: {:.2} rad, shock: {:.2}g", angle, shock);
+//! # }
+//! ```
+//!
+//! ### Wait for the start-up time before reading
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! use kxcj9::{Kxcj9, OutputDataRate, SlaveAddr};
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Kxcj9::new_kxcj9_1018(dev, SlaveAddr::default());
+//! sensor.set_output_data_rate(OutputDataRate::Hz200).unwrap();
+//! // Waits for the start-up time of the configured output data rate
+//! sensor.enable_and_wait(&mut hal::Delay).unwrap();
+//! let acc = sensor.read().unwrap();
+//! # }
+//! ```
+//!
+//! ### Read each new measurement exactly once
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! #[macro_use(block)]
+//! extern crate nb;
+//! use kxcj9::{Kxcj9, SlaveAddr};
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Kxcj9::new_kxcj9_1018(dev, SlaveAddr::default());
+//! sensor.enable_data_ready_interrupt().unwrap();
+//! sensor.enable().unwrap();
+//! loop {
+//!     let acc = block!(sensor.read_new()).unwrap();
+//!     println!("X: {:2}, Y: {:2}, Z: {:2}", acc.x, acc.y, acc.z);
+//! }
+//! # }
+//! ```
+//!
 //! ### Select high resolution
 //!
 //! ```no_run
@@ -172,6 +336,168 @@
 //! # }
 //! ```
 //!
+//! ### Handle interrupt events
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! use kxcj9::{
+//!     Axis, Direction, Event, Kxcj9, SlaveAddr, WakeUpInterruptConfig, WakeUpTriggerMotion,
+//! };
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Kxcj9::new_kxcj9_1008(dev, SlaveAddr::default());
+//! let config = WakeUpInterruptConfig {
+//!     trigger_motion: WakeUpTriggerMotion::none().with(Axis::Z, Direction::Negative),
+//!     ..Default::default()
+//! };
+//! sensor.enable_wake_up_interrupt(config).unwrap();
+//! sensor.enable().unwrap();
+//! loop {
+//!     for event in sensor.poll_events_and_clear().unwrap() {
+//!         if let Event::Motion { axis, direction } = event {
+//!             println!("Motion along {:?} in {:?} direction", axis, direction);
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! ### Wait for the physical interrupt pin
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! #[macro_use(block)]
+//! extern crate nb;
+//! use kxcj9::{InterruptPinPolarity, Kxcj9, SlaveAddr, WakeUpInterruptConfig};
+//! use hal::sysfs_gpio::Direction;
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let pin = hal::Pin::new(23);
+//! pin.export().unwrap();
+//! pin.set_direction(Direction::In).unwrap();
+//! let mut sensor = Kxcj9::new_kxcj9_1008(dev, SlaveAddr::default());
+//! sensor.set_interrupt_pin_polarity(InterruptPinPolarity::ActiveLow).unwrap();
+//! sensor.enable_interrupt_pin().unwrap();
+//! sensor.enable_wake_up_interrupt(WakeUpInterruptConfig::default()).unwrap();
+//! sensor.enable().unwrap();
+//! let mut sensor = sensor.with_interrupt_pin(pin);
+//! loop {
+//!     let info = block!(sensor.wait_for_interrupt()).unwrap();
+//!     println!("Interrupt: {:?}", info);
+//! }
+//! # }
+//! ```
+//!
+//! ### Switch between sleep and active data rates on motion
+//!
+//! ```no_run
+//! extern crate embedded_hal;
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! use embedded_hal::blocking::delay::DelayMs;
+//! use kxcj9::{Kxcj9, OutputDataRate, SlaveAddr, WakeOnMotion, WakeOnMotionConfig};
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Kxcj9::new_kxcj9_1018(dev, SlaveAddr::default());
+//! let mut delay = hal::Delay;
+//! let mut wom = WakeOnMotion::new(WakeOnMotionConfig {
+//!     active_data_rate: OutputDataRate::Hz200,
+//!     inactivity_timeout_ms: 10_000,
+//!     ..Default::default()
+//! });
+//! wom.start(&mut sensor).unwrap();
+//! loop {
+//!     delay.delay_ms(100_u32);
+//!     if let Some(state) = wom.update(&mut sensor, 100).unwrap() {
+//!         println!("Power state changed to {:?}", state);
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! ### Share the I²C bus with other devices
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! use core::cell::RefCell;
+//! use kxcj9::{bus::RefCellDevice, Kxcj9, SlaveAddr};
+//!
+//! # fn main() {
+//! let bus = RefCell::new(hal::I2cdev::new("/dev/i2c-1").unwrap());
+//! let mut boom_start = Kxcj9::new_kxcj9_1018(RefCellDevice::new(&bus), SlaveAddr::default());
+//! let address = SlaveAddr::Alternative(true);
+//! let mut boom_end = Kxcj9::new_kxcj9_1018(RefCellDevice::new(&bus), address);
+//! // Other drivers can use further `RefCellDevice` proxies to the same bus.
+//! boom_start.enable().unwrap();
+//! boom_end.enable().unwrap();
+//! # }
+//! ```
+//!
+//! ### Borrow the I²C bus for each call
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! use kxcj9::{Kxcj9, SlaveAddr};
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let (mut sensor, mut dev) = Kxcj9::new_kxcj9_1018(dev, SlaveAddr::default()).detach();
+//! sensor.with_bus(&mut dev, |sensor| sensor.enable()).unwrap();
+//! // `dev` can be used for other devices in between.
+//! let acc = sensor.with_bus(&mut dev, |sensor| sensor.read()).unwrap();
+//! println!("X: {:2}, Y: {:2}, Z: {:2}", acc.x, acc.y, acc.z);
+//! # }
+//! ```
+//!
+//! ### Drive two devices on the same bus
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! use kxcj9::{Resolution, SensorGroup, SlaveAddr};
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let addresses = [SlaveAddr::Alternative(false), SlaveAddr::Alternative(true)];
+//! let mut group = SensorGroup::new_kxcj9_1018(dev, addresses);
+//! group.for_each(|sensor| sensor.set_resolution(Resolution::High));
+//! group.enable();
+//! for (i, result) in group.read().iter().enumerate() {
+//!     match result {
+//!         Ok(acc) => println!("{}: X: {:2}, Y: {:2}, Z: {:2}", i, acc.x, acc.y, acc.z),
+//!         Err(e) => println!("{}: Error: {:?}", i, e),
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! ### Detect the device variant at runtime
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! use kxcj9::{AnyKxcj9, SlaveAddr};
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = AnyKxcj9::detect(dev, SlaveAddr::default())
+//!     .map_err(|(e, _dev)| e)
+//!     .unwrap();
+//! println!("Variant: {:?}", sensor.variant());
+//! sensor.set_range_at_least(4.0).unwrap();
+//! sensor.enable().unwrap();
+//! let acc = sensor.read().unwrap();
+//! println!("X: {:2}, Y: {:2}, Z: {:2}", acc.x, acc.y, acc.z);
+//! # }
+//! ```
+//!
 //! ### Perform a software reset and wait for it to finish
 //!
 //! ```no_run
@@ -187,24 +513,63 @@
 //! block!(sensor.reset());
 //! # }
 //! ```
+//!
+//! ### Perform a software reset waiting with a delay provider
+//!
+//! ```no_run
+//! extern crate kxcj9;
+//! extern crate linux_embedded_hal as hal;
+//! use kxcj9::{Kxcj9, SlaveAddr};
+//!
+//! # fn main() {
+//! let dev = hal::I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Kxcj9::new_kxcj9_1018(dev, SlaveAddr::default());
+//! let mut delay = hal::Delay;
+//! // Give up if the reset has not finished after 50ms
+//! sensor.reset_blocking(&mut delay, 50).unwrap();
+//! # }
+//! ```
 
 #![deny(unsafe_code, missing_docs)]
 #![no_std]
 
+#[cfg(feature = "critical-section")]
+extern crate critical_section;
+#[cfg(feature = "defmt")]
+extern crate defmt;
 extern crate embedded_hal as hal;
+#[cfg(feature = "async")]
+extern crate embedded_hal_async;
+extern crate libm;
+#[cfg(feature = "log")]
+extern crate log;
+#[cfg(feature = "micromath")]
+extern crate micromath;
+#[cfg(feature = "mint")]
+extern crate mint;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
 extern crate nb;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "uom")]
+extern crate uom;
 use core::marker::PhantomData;
 use hal::blocking::i2c;
 
 mod types;
 pub use types::{
-    Error, GScale16, GScale8, InterruptInfo, InterruptPinLatching, InterruptPinPolarity,
-    Measurement, OutputDataRate, Resolution, SlaveAddr, UnscaledMeasurement, WakeUpInterruptConfig,
+    Axis, DeviceVariant, Direction, Error, Event, GScale16, GScale8, InterruptInfo,
+    InterruptPinLatching, InterruptPinPolarity, Measurement, OutputDataRate, Resolution,
+    SelfTestStage, SettingError, SlaveAddr, UnscaledMeasurement, WakeUpInterruptConfig,
     WakeUpOutputDataRate, WakeUpTriggerMotion,
 };
 
 const DEVICE_BASE_ADDRESS: u8 = 0xE;
 
+/// Standard acceleration of gravity in m/s²
+pub const STANDARD_GRAVITY: f32 = 9.806_65;
+
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 struct Config {
     bits: u8,
@@ -226,11 +591,13 @@ impl Config {
     }
 }
 
-#[doc(hidden)]
+/// Device kinds. See [`ScaledDevice`](../trait.ScaledDevice.html).
 pub mod ic {
     /// Used for KXCJ9-1008 and KXCJB-1041 devices
+    #[derive(Debug)]
     pub struct G8Device(());
     /// Used for KXCJ9-1018 devices
+    #[derive(Debug)]
     pub struct G16Device(());
 }
 
@@ -244,13 +611,66 @@ pub struct Kxcj9<I2C, IC> {
     ctrl2: Config,
     int_ctrl1: Config,
     data_ctrl: u8,
+    int_ctrl2: u8,
+    wakeup_timer: u8,
+    wakeup_threshold: u8,
     was_reset_started: bool,
+    standard_gravity: f32,
     _ic: PhantomData<IC>,
 }
 
+mod any;
+pub use any::AnyKxcj9;
+mod burst_capture;
+pub use burst_capture::{Burst, BurstCapture, BurstCaptureConfig};
+pub mod bus;
+mod capabilities;
+pub use capabilities::Capabilities;
+mod capture_buffer;
+pub use capture_buffer::{CaptureBuffer, CaptureConfig, CaptureTrigger, CaptureWindow};
 mod conversion;
+mod duty_cycle;
+pub use duty_cycle::{DutyCycleConfig, DutyCycleConfigError, DutyCycleSampler};
+mod events;
+pub use events::Events;
+mod group;
+pub use group::SensorGroup;
+mod inactivity;
+pub use inactivity::{InactivityConfig, InactivityDetector, InactivityEvent, MotionComparison};
+mod interrupt_pin;
+pub use interrupt_pin::{InterruptPinError, WithInterruptPin};
+mod timing;
+#[cfg(feature = "uom")]
+mod typed_units;
+#[cfg(feature = "uom")]
+pub use typed_units::AccelerationMeasurement;
+mod trace;
+#[cfg(feature = "defmt")]
+pub use trace::DefmtHook;
+#[cfg(feature = "log")]
+pub use trace::LogHook;
+pub use trace::{
+    AccessKind, MemoryRecorder, RecordedAccess, RegisterAccess, TraceHook, TracingI2c,
+    RECORDED_DATA_LEN,
+};
+mod power;
+pub use power::{
+    BusActivity, PowerConfig, ReadMethod, HIGH_RESOLUTION_CURRENT_UA, LOW_POWER_CURRENT_UA,
+    STANDBY_CURRENT_UA,
+};
+mod probe;
+pub use probe::{probe, ProbeResult};
+mod replay;
+pub use replay::{ReplayError, ReplayI2c, TextRecorder};
+mod retry;
+pub use retry::{RetryPolicy, RetryStats, RetryingI2c};
 mod scaled_device;
+mod supervisor;
+pub use supervisor::{ConfigCheck, Supervisor};
+mod vector;
+mod wake_on_motion;
 pub use scaled_device::ScaledDevice;
+pub use wake_on_motion::{PowerState, WakeOnMotion, WakeOnMotionConfig};
 mod device_impl;
 pub use device_impl::{GScaleConfig, MeasurementBits};
 
@@ -0,0 +1,247 @@
+use crate::{i2c, Kxcj9, OutputDataRate, Resolution, WakeUpOutputDataRate};
+
+/// Typical supply current in standby mode in µA
+pub const STANDBY_CURRENT_UA: f32 = 0.9;
+/// Typical supply current in low power mode (`Resolution::Low`) at the
+/// default output data rate of 50 Hz in µA
+pub const LOW_POWER_CURRENT_UA: f32 = 10.0;
+/// Typical supply current in high resolution mode (`Resolution::High`) in µA
+pub const HIGH_RESOLUTION_CURRENT_UA: f32 = 135.0;
+
+/// Configuration relevant for the power consumption
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerConfig {
+    /// Whether the device is enabled (operating mode) or in standby.
+    pub enabled: bool,
+    /// Configured resolution.
+    pub resolution: Resolution,
+    /// Configured output data rate.
+    pub data_rate: OutputDataRate,
+    /// Output data rate of the wake-up function if it is enabled.
+    pub wake_up_data_rate: Option<WakeUpOutputDataRate>,
+}
+
+impl PowerConfig {
+    /// Resolution the device actually operates at.
+    ///
+    /// Output data rates greater or equal to 400 Hz force the device into
+    /// full power (high resolution) mode.
+    pub fn effective_resolution(&self) -> Resolution {
+        use OutputDataRate as ODR;
+        match self.data_rate {
+            ODR::Hz400 | ODR::Hz800 | ODR::Hz1600 => Resolution::High,
+            _ => self.resolution,
+        }
+    }
+
+    /// Estimated typical supply current in µA.
+    ///
+    /// Only the standby, high resolution and 50 Hz low power currents are
+    /// datasheet figures. In low power mode the current at the other output
+    /// data rates is extrapolated linearly from the 50 Hz value. An enabled
+    /// wake-up function adds the estimated current of sampling at the
+    /// wake-up output data rate in low power mode.
+    pub fn typical_current_ua(&self) -> f32 {
+        if !self.enabled {
+            return STANDBY_CURRENT_UA;
+        }
+        let operating = match self.effective_resolution() {
+            Resolution::Low => low_power_current_ua(self.data_rate),
+            Resolution::High => HIGH_RESOLUTION_CURRENT_UA,
+        };
+        let wake_up = match self.wake_up_data_rate {
+            Some(rate) => low_power_current_ua(rate.into()) - STANDBY_CURRENT_UA,
+            None => 0.0,
+        };
+        operating + wake_up
+    }
+}
+
+/// Estimated typical supply current in low power mode in µA.
+///
+/// The datasheet specifies the current at 50 Hz. The values for the other
+/// output data rates scale the part above the standby current linearly
+/// with the number of measurements per second.
+fn low_power_current_ua(data_rate: OutputDataRate) -> f32 {
+    use OutputDataRate as ODR;
+    match data_rate {
+        ODR::Hz0_781 => 1.04,
+        ODR::Hz1_563 => 1.18,
+        ODR::Hz3_125 => 1.47,
+        ODR::Hz6_25 => 2.04,
+        ODR::Hz12_5 => 3.18,
+        ODR::Hz25 => 5.45,
+        ODR::Hz50 => LOW_POWER_CURRENT_UA,
+        ODR::Hz100 => 19.1,
+        ODR::Hz200 => 37.3,
+        // Always operated in full power mode.
+        ODR::Hz400 | ODR::Hz800 | ODR::Hz1600 => HIGH_RESOLUTION_CURRENT_UA,
+    }
+}
+
+impl From<WakeUpOutputDataRate> for OutputDataRate {
+    fn from(rate: WakeUpOutputDataRate) -> Self {
+        use OutputDataRate as ODR;
+        use WakeUpOutputDataRate as WODR;
+        match rate {
+            WODR::Hz0_781 => ODR::Hz0_781,
+            WODR::Hz1_563 => ODR::Hz1_563,
+            WODR::Hz3_125 => ODR::Hz3_125,
+            WODR::Hz6_25 => ODR::Hz6_25,
+            WODR::Hz12_5 => ODR::Hz12_5,
+            WODR::Hz25 => ODR::Hz25,
+            WODR::Hz50 => ODR::Hz50,
+            WODR::Hz100 => ODR::Hz100,
+        }
+    }
+}
+
+/// Method used to read the measurements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadMethod {
+    /// Read the data registers directly. See `read()`.
+    Read,
+    /// Check the data ready flag and read the data registers.
+    /// See `read_new()`.
+    ReadNew,
+}
+
+impl ReadMethod {
+    /// Number of I²C transactions and bytes transferred per measurement
+    /// including the address bytes.
+    fn transfers(self) -> (u32, u32) {
+        // write_read: address + register + address + data
+        match self {
+            ReadMethod::Read => (1, 3 + 6),
+            ReadMethod::ReadNew => (2, (3 + 1) + (3 + 6)),
+        }
+    }
+}
+
+/// Estimated I²C bus activity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BusActivity {
+    /// I²C transactions per second.
+    pub transactions_per_s: f32,
+    /// Bytes transferred per second including the address bytes.
+    pub bytes_per_s: f32,
+    /// Time the bus is busy per second in µs.
+    pub busy_us_per_s: f32,
+}
+
+impl BusActivity {
+    /// Estimate the I²C bus activity when reading measurements at the
+    /// given rate with the given bus clock frequency.
+    ///
+    /// Each byte takes 9 clock cycles (including the acknowledge bit) and
+    /// the start, repeated start and stop conditions are counted as one
+    /// clock cycle each.
+    pub fn estimate(method: ReadMethod, reads_per_s: f32, bus_frequency_hz: u32) -> Self {
+        let (transactions, bytes) = method.transfers();
+        let clocks = bytes * 9 + transactions * 3;
+        BusActivity {
+            transactions_per_s: transactions as f32 * reads_per_s,
+            bytes_per_s: bytes as f32 * reads_per_s,
+            busy_us_per_s: clocks as f32 * reads_per_s * 1_000_000.0 / bus_frequency_hz as f32,
+        }
+    }
+}
+
+impl<I2C, E, IC> Kxcj9<I2C, IC>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+{
+    /// Current configuration relevant for the power consumption.
+    pub fn power_config(&self) -> PowerConfig {
+        PowerConfig {
+            enabled: self.is_enabled(),
+            resolution: self.resolution(),
+            data_rate: self.output_data_rate(),
+            wake_up_data_rate: self.wake_up_data_rate(),
+        }
+    }
+
+    /// Estimated typical supply current in µA for the current configuration.
+    ///
+    /// See [`PowerConfig::typical_current_ua()`](struct.PowerConfig.html#method.typical_current_ua).
+    pub fn typical_current_ua(&self) -> f32 {
+        self.power_config().typical_current_ua()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(enabled: bool, resolution: Resolution, data_rate: OutputDataRate) -> PowerConfig {
+        PowerConfig {
+            enabled,
+            resolution,
+            data_rate,
+            wake_up_data_rate: None,
+        }
+    }
+
+    #[test]
+    fn standby_current() {
+        let c = config(false, Resolution::High, OutputDataRate::Hz1600);
+        assert_eq!(STANDBY_CURRENT_UA, c.typical_current_ua());
+    }
+
+    #[test]
+    fn operating_current_depends_on_resolution() {
+        let c = config(true, Resolution::Low, OutputDataRate::Hz50);
+        assert_eq!(LOW_POWER_CURRENT_UA, c.typical_current_ua());
+        let c = config(true, Resolution::High, OutputDataRate::Hz50);
+        assert_eq!(HIGH_RESOLUTION_CURRENT_UA, c.typical_current_ua());
+    }
+
+    #[test]
+    fn low_power_current_depends_on_data_rate() {
+        let c = config(true, Resolution::Low, OutputDataRate::Hz0_781);
+        assert_eq!(1.04, c.typical_current_ua());
+        let c = config(true, Resolution::Low, OutputDataRate::Hz12_5);
+        assert_eq!(3.18, c.typical_current_ua());
+        let c = config(true, Resolution::Low, OutputDataRate::Hz200);
+        assert_eq!(37.3, c.typical_current_ua());
+        let c = config(true, Resolution::High, OutputDataRate::Hz0_781);
+        assert_eq!(HIGH_RESOLUTION_CURRENT_UA, c.typical_current_ua());
+    }
+
+    #[test]
+    fn wake_up_function_adds_current() {
+        let mut c = config(true, Resolution::Low, OutputDataRate::Hz12_5);
+        c.wake_up_data_rate = Some(WakeUpOutputDataRate::Hz0_781);
+        assert!((3.18 + 0.14 - c.typical_current_ua()).abs() < 1e-4);
+        c.wake_up_data_rate = Some(WakeUpOutputDataRate::Hz100);
+        assert!((3.18 + 18.2 - c.typical_current_ua()).abs() < 1e-4);
+        c.enabled = false;
+        assert_eq!(STANDBY_CURRENT_UA, c.typical_current_ua());
+    }
+
+    #[test]
+    fn high_data_rates_force_full_power() {
+        let c = config(true, Resolution::Low, OutputDataRate::Hz400);
+        assert_eq!(Resolution::High, c.effective_resolution());
+        assert_eq!(HIGH_RESOLUTION_CURRENT_UA, c.typical_current_ua());
+        let c = config(true, Resolution::Low, OutputDataRate::Hz200);
+        assert_eq!(Resolution::Low, c.effective_resolution());
+    }
+
+    #[test]
+    fn can_estimate_bus_activity() {
+        let activity = BusActivity::estimate(ReadMethod::Read, 100.0, 400_000);
+        assert_eq!(100.0, activity.transactions_per_s);
+        assert_eq!(900.0, activity.bytes_per_s);
+        // (9 * 9 + 3) clocks * 100 / 400 kHz
+        assert_eq!(21_000.0, activity.busy_us_per_s);
+    }
+
+    #[test]
+    fn read_new_needs_more_bus_activity() {
+        let activity = BusActivity::estimate(ReadMethod::ReadNew, 10.0, 100_000);
+        assert_eq!(20.0, activity.transactions_per_s);
+        assert_eq!(130.0, activity.bytes_per_s);
+        assert_eq!(12_300.0, activity.busy_us_per_s);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::device_impl::Register;
+use crate::{i2c, DeviceVariant, SlaveAddr, DEVICE_BASE_ADDRESS};
+
+/// Value of the `DCST_RESP` register while no self-test is running
+const DCST_RESP_DEFAULT: u8 = 0x55;
+
+/// Result of probing one address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeResult<E> {
+    /// Probed address
+    pub address: SlaveAddr,
+    /// `WHO_AM_I` register value or `None` if the device did not respond.
+    pub who_am_i: Option<u8>,
+    /// `DCST_RESP` register value or `None` if the device did not respond.
+    pub dcst_resp: Option<u8>,
+    /// I²C error which ended probing this address, if any.
+    ///
+    /// This is usually a missing acknowledge because no device is present,
+    /// but it may also be another bus error.
+    pub error: Option<E>,
+}
+
+impl<E> ProbeResult<E> {
+    /// Whether a device responded at this address.
+    pub fn is_present(&self) -> bool {
+        self.who_am_i.is_some()
+    }
+
+    /// Device variant present at this address.
+    ///
+    /// Returns `None` if no device responded, the `WHO_AM_I` value is
+    /// unknown or `DCST_RESP` does not contain `0x55`.
+    pub fn variant(&self) -> Option<DeviceVariant> {
+        if self.dcst_resp == Some(DCST_RESP_DEFAULT) {
+            self.who_am_i.and_then(DeviceVariant::from_who_am_i)
+        } else {
+            None
+        }
+    }
+}
+
+/// Scan the I²C bus for devices.
+///
+/// The `WHO_AM_I` and `DCST_RESP` registers are read at each of the
+/// possible addresses. `SlaveAddr::Default` and `SlaveAddr::Alternative(false)`
+/// are the same address so the results correspond to `SlaveAddr::Default`
+/// and `SlaveAddr::Alternative(true)`.
+///
+/// A device is considered present if reading `WHO_AM_I` succeeds. As the
+/// I²C traits do not distinguish a missing acknowledge from other bus
+/// errors, the error is kept in the result so that the caller can tell them
+/// apart. No register is written so the configuration of the devices is
+/// not altered.
+pub fn probe<I2C, E>(i2c: &mut I2C) -> [ProbeResult<E>; 2]
+where
+    I2C: i2c::WriteRead<Error = E>,
+{
+    [SlaveAddr::Default, SlaveAddr::Alternative(true)].map(|address| probe_address(i2c, address))
+}
+
+fn probe_address<I2C, E>(i2c: &mut I2C, address: SlaveAddr) -> ProbeResult<E>
+where
+    I2C: i2c::WriteRead<Error = E>,
+{
+    let address_byte = address.addr(DEVICE_BASE_ADDRESS);
+    let mut result = ProbeResult {
+        address,
+        who_am_i: None,
+        dcst_resp: None,
+        error: None,
+    };
+    let mut read = |register| {
+        let mut data = [0];
+        i2c.write_read(address_byte, &[register], &mut data)
+            .map(|_| data[0])
+    };
+    match read(Register::WHO_AM_I) {
+        Ok(who_am_i) => result.who_am_i = Some(who_am_i),
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    }
+    match read(Register::DCST_RESP) {
+        Ok(dcst_resp) => result.dcst_resp = Some(dcst_resp),
+        Err(e) => result.error = Some(e),
+    }
+    result
+}
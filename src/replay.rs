@@ -0,0 +1,322 @@
+use crate::{AccessKind, RegisterAccess, TraceHook};
+use core::fmt;
+use core::iter::Enumerate;
+use core::str::{Lines, SplitWhitespace};
+use hal::blocking::i2c::{Write, WriteRead};
+
+/// Trace hook recording every register access in a text format
+///
+/// Each access is written as one line containing `w` for writes or `r`
+/// for reads followed by the device address, the register and the data
+/// bytes in hexadecimal. Failed accesses are prefixed with `!`. For example:
+///
+/// ```text
+/// w 0e 1b 80
+/// r 0e 06 10 00 20 00 30 00
+/// !r 0e 0f 00
+/// ```
+///
+/// All data bytes are written regardless of the length of the transfer.
+/// Empty lines and lines starting with `#` are ignored when replaying.
+/// Use it with [`Kxcj9::with_trace_hook()`] or [`TracingI2c`] and replay
+/// the recording with [`ReplayI2c`].
+///
+/// [`Kxcj9::with_trace_hook()`]: struct.Kxcj9.html#method.with_trace_hook
+/// [`TracingI2c`]: struct.TracingI2c.html
+/// [`ReplayI2c`]: struct.ReplayI2c.html
+#[derive(Debug)]
+pub struct TextRecorder<W> {
+    writer: W,
+    has_error: bool,
+}
+
+impl<W: fmt::Write> TextRecorder<W> {
+    /// Create a new recorder writing into the given writer.
+    pub fn new(writer: W) -> Self {
+        TextRecorder {
+            writer,
+            has_error: false,
+        }
+    }
+
+    /// Destroy the recorder, return the writer.
+    pub fn destroy(self) -> W {
+        self.writer
+    }
+
+    /// Get a reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Whether writing into the writer has failed at least once.
+    pub fn has_error(&self) -> bool {
+        self.has_error
+    }
+
+    fn write_access(&mut self, access: &RegisterAccess<'_>) -> fmt::Result {
+        let failed = if access.success { "" } else { "!" };
+        let kind = match access.kind {
+            AccessKind::Read => 'r',
+            AccessKind::Write => 'w',
+        };
+        write!(
+            self.writer,
+            "{}{} {:02x} {:02x}",
+            failed, kind, access.address, access.register
+        )?;
+        for byte in access.data {
+            write!(self.writer, " {:02x}", byte)?;
+        }
+        writeln!(self.writer)
+    }
+}
+
+impl<W: fmt::Write> TraceHook for TextRecorder<W> {
+    fn trace(&mut self, access: &RegisterAccess<'_>) {
+        if self.write_access(access).is_err() {
+            self.has_error = true;
+        }
+    }
+}
+
+/// Error replaying a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The transaction does not match the kind, address or register of
+    /// the recorded access at this line
+    UnexpectedTransaction {
+        /// Line number in the recording
+        line: usize,
+    },
+    /// The recorded access at this line failed
+    RecordedError {
+        /// Line number in the recording
+        line: usize,
+    },
+    /// The line cannot be parsed
+    InvalidRecording {
+        /// Line number in the recording
+        line: usize,
+    },
+    /// All recorded accesses have been replayed
+    EndOfRecording,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::UnexpectedTransaction { line } => {
+                write!(f, "transaction does not match line {}", line)
+            }
+            ReplayError::RecordedError { line } => write!(f, "recorded error at line {}", line),
+            ReplayError::InvalidRecording { line } => {
+                write!(f, "invalid recording at line {}", line)
+            }
+            ReplayError::EndOfRecording => write!(f, "end of recording"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReplayError {}
+
+/// I²C bus implementation replaying a recording
+///
+/// Reads return the recorded data. Writes whose data differs from the
+/// recording are counted as divergent and the replay continues.
+/// Transactions to a different address or register, failed recorded
+/// accesses and unparseable lines return a [`ReplayError`].
+///
+/// See [`TextRecorder`] for the format.
+///
+/// [`ReplayError`]: enum.ReplayError.html
+/// [`TextRecorder`]: struct.TextRecorder.html
+#[derive(Debug, Clone)]
+pub struct ReplayI2c<'a> {
+    lines: Enumerate<Lines<'a>>,
+    divergences: usize,
+    first_divergence: Option<usize>,
+}
+
+impl<'a> ReplayI2c<'a> {
+    /// Create a new replay of a recording.
+    pub fn new(recording: &'a str) -> Self {
+        ReplayI2c {
+            lines: recording.lines().enumerate(),
+            divergences: 0,
+            first_divergence: None,
+        }
+    }
+
+    /// Number of writes which differed from the recording.
+    pub fn divergences(&self) -> usize {
+        self.divergences
+    }
+
+    /// Line number of the first write which differed from the recording.
+    pub fn first_divergence(&self) -> Option<usize> {
+        self.first_divergence
+    }
+
+    /// Whether all recorded accesses have been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.lines.clone().all(|(_, line)| is_ignored(line.trim()))
+    }
+
+    fn next_access(
+        &mut self,
+        kind: AccessKind,
+        address: u8,
+    ) -> Result<(usize, ReplayedAccess<'a>), ReplayError> {
+        for (index, line) in &mut self.lines {
+            let line_number = index + 1;
+            let line = line.trim();
+            if is_ignored(line) {
+                continue;
+            }
+            let access =
+                parse_access(line).ok_or(ReplayError::InvalidRecording { line: line_number })?;
+            if access.kind != kind || access.address != address {
+                return Err(ReplayError::UnexpectedTransaction { line: line_number });
+            }
+            return Ok((line_number, access));
+        }
+        Err(ReplayError::EndOfRecording)
+    }
+}
+
+fn parse_byte(token: &str) -> Option<u8> {
+    u8::from_str_radix(token, 16).ok()
+}
+
+fn is_ignored(line: &str) -> bool {
+    line.is_empty() || line.starts_with('#')
+}
+
+/// Parsed line of a recording. The data bytes are parsed on demand so
+/// that transfers of any length can be replayed.
+#[derive(Debug, Clone)]
+struct ReplayedAccess<'a> {
+    kind: AccessKind,
+    address: u8,
+    register: u8,
+    data: SplitWhitespace<'a>,
+    success: bool,
+}
+
+impl<'a> ReplayedAccess<'a> {
+    fn data(&self) -> impl Iterator<Item = u8> + 'a {
+        self.data
+            .clone()
+            .map(|token| parse_byte(token).unwrap_or(0))
+    }
+
+    fn data_len(&self) -> usize {
+        self.data.clone().count()
+    }
+}
+
+fn parse_access(line: &str) -> Option<ReplayedAccess<'_>> {
+    let (success, line) = match line.strip_prefix('!') {
+        Some(line) => (false, line),
+        None => (true, line),
+    };
+    let mut tokens = line.split_whitespace();
+    let kind = match tokens.next()? {
+        "r" => AccessKind::Read,
+        "w" => AccessKind::Write,
+        _ => return None,
+    };
+    let address = parse_byte(tokens.next()?)?;
+    let register = parse_byte(tokens.next()?)?;
+    if !tokens.clone().all(|token| parse_byte(token).is_some()) {
+        return None;
+    }
+    Some(ReplayedAccess {
+        kind,
+        address,
+        register,
+        data: tokens,
+        success,
+    })
+}
+
+impl<'a> Write for ReplayI2c<'a> {
+    type Error = ReplayError;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        let (line, access) = self.next_access(AccessKind::Write, address)?;
+        match bytes.split_first() {
+            Some((&register, data)) if register == access.register => {
+                if !access.data().eq(data.iter().cloned()) {
+                    self.divergences += 1;
+                    self.first_divergence.get_or_insert(line);
+                }
+            }
+            _ => return Err(ReplayError::UnexpectedTransaction { line }),
+        }
+        if access.success {
+            Ok(())
+        } else {
+            Err(ReplayError::RecordedError { line })
+        }
+    }
+}
+
+impl<'a> WriteRead for ReplayI2c<'a> {
+    type Error = ReplayError;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let (line, access) = self.next_access(AccessKind::Read, address)?;
+        if bytes != [access.register] || buffer.len() != access.data_len() {
+            return Err(ReplayError::UnexpectedTransaction { line });
+        }
+        if !access.success {
+            return Err(ReplayError::RecordedError { line });
+        }
+        for (byte, value) in buffer.iter_mut().zip(access.data()) {
+            *byte = value;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_accesses() {
+        let access = parse_access("w 0e 1b 80").unwrap();
+        assert_eq!(AccessKind::Write, access.kind);
+        assert_eq!(0x0E, access.address);
+        assert_eq!(0x1B, access.register);
+        assert!(access.data().eq([0x80].iter().cloned()));
+        assert!(access.success);
+        let access = parse_access("!r 0f 06 01 02 03 04 05 06").unwrap();
+        assert_eq!(AccessKind::Read, access.kind);
+        assert!(access.data().eq(1..=6));
+        assert!(!access.success);
+    }
+
+    #[test]
+    fn can_parse_long_accesses() {
+        let access = parse_access("r 0e 06 01 02 03 04 05 06 07 08").unwrap();
+        assert_eq!(8, access.data_len());
+        assert!(access.data().eq(1..=8));
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert!(parse_access("x 0e 1b").is_none());
+        assert!(parse_access("w 0e").is_none());
+        assert!(parse_access("w 0e 1g").is_none());
+        assert!(parse_access("r 0e 06 01 0x").is_none());
+    }
+}
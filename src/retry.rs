@@ -0,0 +1,243 @@
+use crate::device_impl::{BitFlags, Register};
+use crate::{SlaveAddr, DEVICE_BASE_ADDRESS};
+use hal::blocking::delay::DelayUs;
+use hal::blocking::i2c::{Read, Write, WriteRead};
+
+/// Retry policy for transient I²C errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts per transaction including the first one.
+    ///
+    /// A value of 0 is treated as 1.
+    pub max_attempts: u8,
+    /// Delay before the first retry in microseconds. The delay is doubled
+    /// before each further retry.
+    pub backoff_us: u32,
+    /// Whether register reads are retried.
+    ///
+    /// Reads of the `INT_REL` and `DCST_RESP` registers of a KXCJ9 are
+    /// never retried because they have side effects.
+    pub retry_reads: bool,
+    /// Whether register writes are retried.
+    ///
+    /// Writes to a KXCJ9 which start a software reset or the communication
+    /// self-test are never retried because they are not idempotent.
+    pub retry_writes: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            backoff_us: 100,
+            retry_reads: true,
+            retry_writes: true,
+        }
+    }
+}
+
+/// Retry counters
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RetryStats {
+    /// Number of transactions repeated after an error.
+    pub retries: u32,
+    /// Number of transactions which failed after all attempts.
+    pub failures: u32,
+}
+
+/// I²C bus wrapper retrying transactions which fail
+///
+/// Failed transactions are repeated according to the [`RetryPolicy`]
+/// waiting in between using the delay provider. The error of the last
+/// attempt is returned if all attempts fail.
+///
+/// The counters can be read while the driver owns the wrapper through
+/// [`Kxcj9::i2c()`].
+///
+/// [`RetryPolicy`]: struct.RetryPolicy.html
+/// [`Kxcj9::i2c()`]: struct.Kxcj9.html#method.i2c
+#[derive(Debug)]
+pub struct RetryingI2c<I2C, D> {
+    i2c: I2C,
+    delay: D,
+    policy: RetryPolicy,
+    stats: RetryStats,
+}
+
+impl<I2C, D> RetryingI2c<I2C, D> {
+    /// Create a new wrapper.
+    pub fn new(i2c: I2C, delay: D, policy: RetryPolicy) -> Self {
+        RetryingI2c {
+            i2c,
+            delay,
+            policy,
+            stats: RetryStats::default(),
+        }
+    }
+
+    /// Destroy the wrapper, return the I²C bus and delay instances.
+    pub fn destroy(self) -> (I2C, D) {
+        (self.i2c, self.delay)
+    }
+
+    /// Current retry policy.
+    pub fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Set the retry policy.
+    pub fn set_policy(&mut self, policy: RetryPolicy) {
+        self.policy = policy;
+    }
+
+    /// Retry counters.
+    pub fn stats(&self) -> RetryStats {
+        self.stats
+    }
+
+    /// Reset the retry counters.
+    pub fn reset_stats(&mut self) {
+        self.stats = RetryStats::default();
+    }
+}
+
+impl<I2C, D: DelayUs<u32>> RetryingI2c<I2C, D> {
+    fn run<E, F>(&mut self, retry: bool, mut transaction: F) -> Result<(), E>
+    where
+        F: FnMut(&mut I2C) -> Result<(), E>,
+    {
+        let max_attempts = if retry {
+            self.policy.max_attempts.max(1)
+        } else {
+            1
+        };
+        let mut backoff_us = self.policy.backoff_us;
+        let mut attempt = 1;
+        loop {
+            match transaction(&mut self.i2c) {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= max_attempts => {
+                    self.stats.failures = self.stats.failures.saturating_add(1);
+                    return Err(e);
+                }
+                Err(_) => {
+                    self.stats.retries = self.stats.retries.saturating_add(1);
+                    attempt += 1;
+                    self.delay.delay_us(backoff_us);
+                    backoff_us = backoff_us.saturating_mul(2);
+                }
+            }
+        }
+    }
+}
+
+/// Transactions to other devices on the bus are left to the retry policy.
+fn is_kxcj9_address(address: u8) -> bool {
+    address == SlaveAddr::Default.addr(DEVICE_BASE_ADDRESS)
+        || address == SlaveAddr::Alternative(true).addr(DEVICE_BASE_ADDRESS)
+}
+
+fn is_idempotent_write(address: u8, bytes: &[u8]) -> bool {
+    if !is_kxcj9_address(address) {
+        return true;
+    }
+    match bytes {
+        [Register::CTRL2, value, ..] => value & (BitFlags::SRST | BitFlags::DCST) == 0,
+        _ => true,
+    }
+}
+
+fn is_idempotent_read(address: u8, bytes: &[u8], len: usize) -> bool {
+    if !is_kxcj9_address(address) {
+        return true;
+    }
+    match bytes {
+        // Reading releases the interrupts or finishes the self-test.
+        [start, ..] => [Register::INT_REL, Register::DCST_RESP]
+            .iter()
+            .all(|&register| register < *start || usize::from(register - start) >= len),
+        _ => true,
+    }
+}
+
+impl<I2C: Read, D: DelayUs<u32>> Read for RetryingI2c<I2C, D> {
+    type Error = I2C::Error;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        let retry = self.policy.retry_reads;
+        self.run(retry, |i2c| i2c.read(address, buffer))
+    }
+}
+
+impl<I2C: Write, D: DelayUs<u32>> Write for RetryingI2c<I2C, D> {
+    type Error = I2C::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        let retry = self.policy.retry_writes && is_idempotent_write(address, bytes);
+        self.run(retry, |i2c| i2c.write(address, bytes))
+    }
+}
+
+impl<I2C: WriteRead, D: DelayUs<u32>> WriteRead for RetryingI2c<I2C, D> {
+    type Error = I2C::Error;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let retry = self.policy.retry_reads && is_idempotent_read(address, bytes, buffer.len());
+        self.run(retry, |i2c| i2c.write_read(address, bytes, buffer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR: u8 = 0x0E;
+    const ALT_ADDR: u8 = 0x0F;
+    const OTHER_ADDR: u8 = 0x1E;
+
+    #[test]
+    fn register_writes_are_idempotent() {
+        assert!(is_idempotent_write(ADDR, &[Register::CTRL1, 0xFF]));
+        assert!(is_idempotent_write(ADDR, &[Register::CTRL2, 0b0000_0111]));
+    }
+
+    #[test]
+    fn reset_and_self_test_are_not_idempotent() {
+        assert!(!is_idempotent_write(
+            ADDR,
+            &[Register::CTRL2, BitFlags::SRST]
+        ));
+        assert!(!is_idempotent_write(
+            ALT_ADDR,
+            &[Register::CTRL2, BitFlags::DCST | 1]
+        ));
+    }
+
+    #[test]
+    fn writes_to_other_devices_are_left_to_policy() {
+        assert!(is_idempotent_write(
+            OTHER_ADDR,
+            &[Register::CTRL2, BitFlags::SRST]
+        ));
+    }
+
+    #[test]
+    fn data_reads_are_idempotent() {
+        assert!(is_idempotent_read(ADDR, &[Register::XOUT_L], 6));
+        assert!(is_idempotent_read(ADDR, &[Register::INT_SOURCE1], 2));
+        assert!(is_idempotent_read(ADDR, &[Register::WHO_AM_I], 1));
+    }
+
+    #[test]
+    fn side_effecting_reads_are_not_idempotent() {
+        assert!(!is_idempotent_read(ADDR, &[Register::INT_REL], 1));
+        assert!(!is_idempotent_read(ALT_ADDR, &[Register::DCST_RESP], 1));
+        assert!(!is_idempotent_read(ADDR, &[Register::INT_SOURCE1], 5));
+        assert!(is_idempotent_read(OTHER_ADDR, &[Register::INT_REL], 1));
+    }
+}
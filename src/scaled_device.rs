@@ -1,17 +1,70 @@
-use {ic, private, Error, GScaleConfig, Measurement, MeasurementBits, UnscaledMeasurement};
-
-#[doc(hidden)]
+use crate::{
+    ic, private, Capabilities, DeviceVariant, Error, GScale16, GScale8, GScaleConfig, Measurement,
+    MeasurementBits, SettingError, UnscaledMeasurement,
+};
+
+/// Device kind with its G scales and capabilities
+///
+/// Implemented by [`ic::G8Device`] and [`ic::G16Device`]. Generic code over
+/// `Kxcj9<I2C, IC>` needs an `IC: ScaledDevice` bound to use the methods
+/// depending on the device kind like `set_scale()` or `read()`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+///
+/// [`ic::G8Device`]: ic/struct.G8Device.html
+/// [`ic::G16Device`]: ic/struct.G16Device.html
 pub trait ScaledDevice: private::Sealed {
+    /// G scale type of the devices of this kind.
+    ///
+    /// [`GScale8`](enum.GScale8.html) for `G8Device` and
+    /// [`GScale16`](enum.GScale16.html) for `G16Device`.
+    type Scale: Copy;
+
+    /// Scales without full power in ascending order matching
+    /// `CAPABILITIES.ranges_g`.
+    const SCALES: [Self::Scale; 3];
+
+    /// G range selection bits for a scale.
+    fn get_scale_config(scale: Self::Scale) -> GScaleConfig;
+
+    /// Scale a measurement read with the given bits per measurement and
+    /// G range selection to g.
     fn get_scaled(
         unscaled: UnscaledMeasurement,
         bits: MeasurementBits,
         scale_config: GScaleConfig,
     ) -> Measurement;
 
+    /// Wake-up threshold register value for a threshold in g.
+    ///
+    /// Returns `Error::InvalidSetting` if the threshold is out of range.
     fn get_wake_up_threshold<E>(threshold: f32) -> Result<u8, Error<E>>;
+
+    /// Device variants of this kind.
+    const VARIANTS: &'static [DeviceVariant];
+
+    /// Capabilities of the devices of this kind.
+    const CAPABILITIES: Capabilities;
 }
 
 impl ScaledDevice for ic::G8Device {
+    // KXCJ9-1008 and KXCJB-1041
+    type Scale = GScale8;
+    const SCALES: [GScale8; 3] = [GScale8::G2, GScale8::G4, GScale8::G8];
+    const VARIANTS: &'static [DeviceVariant] =
+        &[DeviceVariant::Kxcj9_1008, DeviceVariant::Kxcjb_1041];
+    // Both variants share the same capabilities.
+    const CAPABILITIES: Capabilities = Capabilities::KXCJ9_1008;
+
+    fn get_scale_config(scale: GScale8) -> GScaleConfig {
+        match scale {
+            GScale8::G2 => GScaleConfig::_0,
+            GScale8::G4 => GScaleConfig::_1,
+            GScale8::G8 => GScaleConfig::_2,
+            GScale8::G8FP => GScaleConfig::_3,
+        }
+    }
+
     fn get_scaled(
         unscaled: UnscaledMeasurement,
         bits: MeasurementBits,
@@ -31,9 +84,10 @@ impl ScaledDevice for ic::G8Device {
         }
     }
 
+    #[allow(clippy::manual_range_contains)]
     fn get_wake_up_threshold<E>(threshold: f32) -> Result<u8, Error<E>> {
         if threshold < 0.0 || threshold > 8.0 {
-            Err(Error::InvalidSetting)
+            Err(Error::InvalidSetting(SettingError::ThresholdOutOfRange))
         } else {
             Ok((threshold * 16.0 + 0.5) as u8)
         }
@@ -41,6 +95,20 @@ impl ScaledDevice for ic::G8Device {
 }
 
 impl ScaledDevice for ic::G16Device {
+    type Scale = GScale16;
+    const SCALES: [GScale16; 3] = [GScale16::G4, GScale16::G8, GScale16::G16];
+    const VARIANTS: &'static [DeviceVariant] = &[DeviceVariant::Kxcj9_1018];
+    const CAPABILITIES: Capabilities = Capabilities::KXCJ9_1018;
+
+    fn get_scale_config(scale: GScale16) -> GScaleConfig {
+        match scale {
+            GScale16::G4 => GScaleConfig::_0,
+            GScale16::G8 => GScaleConfig::_1,
+            GScale16::G16 => GScaleConfig::_2,
+            GScale16::G16FP => GScaleConfig::_3,
+        }
+    }
+
     fn get_scaled(
         unscaled: UnscaledMeasurement,
         bits: MeasurementBits,
@@ -60,11 +128,12 @@ impl ScaledDevice for ic::G16Device {
         }
     }
 
+    #[allow(clippy::manual_range_contains)]
     fn get_wake_up_threshold<E>(threshold: f32) -> Result<u8, Error<E>> {
         // There is a mismatch in the datasheet for the KXCJ9-1018 model.
         // Kionix engineers confirmed me that the reset value corresponds to 1g.
         if threshold < 0.0 || threshold > 16.0 {
-            Err(Error::InvalidSetting)
+            Err(Error::InvalidSetting(SettingError::ThresholdOutOfRange))
         } else {
             Ok((threshold * 8.0 + 0.5) as u8)
         }
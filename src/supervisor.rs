@@ -0,0 +1,103 @@
+use crate::{i2c, Error, Kxcj9};
+
+/// Result of comparing the configuration registers of the device with
+/// the configuration cached in the driver
+///
+/// Each field is `true` if the register matches the cached value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigCheck {
+    /// `CTRL1` register matches.
+    pub ctrl1: bool,
+    /// `CTRL2` register matches.
+    pub ctrl2: bool,
+    /// `DATA_CTRL` register matches.
+    pub data_ctrl: bool,
+    /// `INT_CTRL1` register matches.
+    pub int_ctrl1: bool,
+    /// `INT_CTRL2` register matches.
+    pub int_ctrl2: bool,
+}
+
+impl ConfigCheck {
+    /// Whether all registers match.
+    pub fn matches(&self) -> bool {
+        self.ctrl1 && self.ctrl2 && self.data_ctrl && self.int_ctrl1 && self.int_ctrl2
+    }
+}
+
+/// Supervisor recovering from unexpected device resets
+///
+/// An unexpected reset (for example due to a brown-out on the supply)
+/// returns the device registers to their default values while the driver
+/// still holds the previous configuration. The supervisor periodically
+/// compares the configuration registers with the driver and writes the
+/// full configuration to the device again if they differ.
+///
+/// The supervisor does not own the driver. Call [`update()`] periodically
+/// with the time elapsed since the previous call.
+///
+/// [`update()`]: #method.update
+#[derive(Debug, Clone)]
+pub struct Supervisor {
+    check_interval_ms: u32,
+    elapsed_ms: u32,
+    recovery_count: u32,
+}
+
+impl Supervisor {
+    /// Create a new supervisor checking the configuration every
+    /// `check_interval_ms` milliseconds.
+    pub fn new(check_interval_ms: u32) -> Self {
+        Supervisor {
+            check_interval_ms,
+            elapsed_ms: 0,
+            recovery_count: 0,
+        }
+    }
+
+    /// Number of times the configuration has been restored.
+    pub fn recovery_count(&self) -> u32 {
+        self.recovery_count
+    }
+
+    /// Advance the supervisor and check the configuration once the check
+    /// interval has elapsed.
+    ///
+    /// Returns the result of the check if the configuration did not match
+    /// and has been restored.
+    pub fn update<I2C, E, IC>(
+        &mut self,
+        sensor: &mut Kxcj9<I2C, IC>,
+        elapsed_ms: u32,
+    ) -> Result<Option<ConfigCheck>, Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    {
+        self.elapsed_ms = self.elapsed_ms.saturating_add(elapsed_ms);
+        if self.elapsed_ms < self.check_interval_ms {
+            return Ok(None);
+        }
+        self.check(sensor)
+    }
+
+    /// Check the configuration immediately and restore it if it does
+    /// not match.
+    ///
+    /// Returns the result of the check if the configuration has been restored.
+    pub fn check<I2C, E, IC>(
+        &mut self,
+        sensor: &mut Kxcj9<I2C, IC>,
+    ) -> Result<Option<ConfigCheck>, Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    {
+        self.elapsed_ms = 0;
+        let check = sensor.verify_config()?;
+        if check.matches() {
+            return Ok(None);
+        }
+        sensor.restore_config()?;
+        self.recovery_count = self.recovery_count.saturating_add(1);
+        Ok(Some(check))
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{
+    i2c, Error, Kxcj9, OutputDataRate, Resolution, ScaledDevice, WakeUpInterruptConfig,
+    WakeUpOutputDataRate,
+};
+use hal::blocking::delay::DelayUs;
+
+impl OutputDataRate {
+    /// Time between two consecutive measurements in microseconds.
+    pub fn period_us(self) -> u32 {
+        use OutputDataRate as ODR;
+        match self {
+            ODR::Hz0_781 => 1_280_000,
+            ODR::Hz1_563 => 640_000,
+            ODR::Hz3_125 => 320_000,
+            ODR::Hz6_25 => 160_000,
+            ODR::Hz12_5 => 80_000,
+            ODR::Hz25 => 40_000,
+            ODR::Hz50 => 20_000,
+            ODR::Hz100 => 10_000,
+            ODR::Hz200 => 5_000,
+            ODR::Hz400 => 2_500,
+            ODR::Hz800 => 1_250,
+            ODR::Hz1600 => 625,
+        }
+    }
+
+    /// Typical time in microseconds from enabling the device until the
+    /// first valid measurement is available.
+    ///
+    /// Output data rates greater or equal to 400 Hz force the device into
+    /// full power mode so the resolution has no effect for them.
+    pub fn start_up_time_us(self, resolution: Resolution) -> u32 {
+        use OutputDataRate as ODR;
+        match (self, resolution) {
+            (ODR::Hz0_781, Resolution::Low) => 1_280_300,
+            (ODR::Hz1_563, Resolution::Low) => 640_300,
+            (ODR::Hz3_125, Resolution::Low) => 320_300,
+            (ODR::Hz6_25, Resolution::Low) => 160_300,
+            (ODR::Hz12_5, Resolution::Low) => 80_300,
+            (ODR::Hz25, Resolution::Low) => 40_300,
+            (ODR::Hz50, Resolution::Low) => 20_300,
+            (ODR::Hz100, Resolution::Low) => 10_300,
+            (ODR::Hz200, Resolution::Low) => 5_300,
+            (ODR::Hz0_781, _) => 1_281_200,
+            (ODR::Hz1_563, _) => 641_200,
+            (ODR::Hz3_125, _) => 321_200,
+            (ODR::Hz6_25, _) => 161_200,
+            (ODR::Hz12_5, _) => 81_200,
+            (ODR::Hz25, _) => 41_200,
+            (ODR::Hz50, _) => 21_200,
+            (ODR::Hz100, _) => 11_200,
+            (ODR::Hz200, _) => 6_200,
+            (ODR::Hz400, _) => 3_700,
+            (ODR::Hz800, _) => 2_450,
+            (ODR::Hz1600, _) => 1_825,
+        }
+    }
+
+    pub(crate) fn from_data_ctrl(data_ctrl: u8) -> Self {
+        use OutputDataRate as ODR;
+        match data_ctrl & 0b000_1111 {
+            0b000_1000 => ODR::Hz0_781,
+            0b000_1001 => ODR::Hz1_563,
+            0b000_1010 => ODR::Hz3_125,
+            0b000_1011 => ODR::Hz6_25,
+            0 => ODR::Hz12_5,
+            0b000_0001 => ODR::Hz25,
+            0b000_0011 => ODR::Hz100,
+            0b000_0100 => ODR::Hz200,
+            0b000_0101 => ODR::Hz400,
+            0b000_0110 => ODR::Hz800,
+            0b000_0111 => ODR::Hz1600,
+            _ => ODR::Hz50,
+        }
+    }
+}
+
+impl WakeUpOutputDataRate {
+    /// Time between two consecutive wake-up motion checks in milliseconds.
+    pub fn period_ms(self) -> u32 {
+        use crate::WakeUpOutputDataRate as ODR;
+        match self {
+            ODR::Hz0_781 => 1280,
+            ODR::Hz1_563 => 640,
+            ODR::Hz3_125 => 320,
+            ODR::Hz6_25 => 160,
+            ODR::Hz12_5 => 80,
+            ODR::Hz25 => 40,
+            ODR::Hz50 => 20,
+            ODR::Hz100 => 10,
+        }
+    }
+}
+
+pub(crate) const WAKE_UP_DATA_RATES: [WakeUpOutputDataRate; 8] = [
+    WakeUpOutputDataRate::Hz0_781,
+    WakeUpOutputDataRate::Hz1_563,
+    WakeUpOutputDataRate::Hz3_125,
+    WakeUpOutputDataRate::Hz6_25,
+    WakeUpOutputDataRate::Hz12_5,
+    WakeUpOutputDataRate::Hz25,
+    WakeUpOutputDataRate::Hz50,
+    WakeUpOutputDataRate::Hz100,
+];
+
+impl WakeUpInterruptConfig {
+    /// Create a default configuration with the fault count and data rate
+    /// fitted to the given delay in milliseconds.
+    ///
+    /// See [`fit_delay_ms()`](#method.fit_delay_ms). The achieved delay
+    /// can be retrieved with [`delay_ms()`](#method.delay_ms).
+    pub fn from_delay_ms(delay_ms: u32) -> Self {
+        let mut config = WakeUpInterruptConfig::default();
+        config.fit_delay_ms(delay_ms);
+        config
+    }
+
+    /// Delay in milliseconds from the start of the motion until the
+    /// wake-up interrupt is triggered.
+    ///
+    /// This is `fault_count` periods of the wake-up data rate.
+    pub fn delay_ms(&self) -> u32 {
+        u32::from(self.fault_count) * self.data_rate.period_ms()
+    }
+
+    /// Set the fault count closest to the given delay in milliseconds
+    /// for the configured data rate and return the achieved delay.
+    ///
+    /// The fault count is limited to the range `[1-255]`.
+    pub fn set_delay_ms(&mut self, delay_ms: u32) -> u32 {
+        self.fault_count = fault_count_for(delay_ms, self.data_rate);
+        self.delay_ms()
+    }
+
+    /// Select the data rate and fault count achieving the delay closest to
+    /// the given delay in milliseconds and return the achieved delay.
+    ///
+    /// If several data rates achieve the same delay, the lowest one
+    /// is selected, since it uses less power.
+    pub fn fit_delay_ms(&mut self, delay_ms: u32) -> u32 {
+        let mut best = (self.data_rate, self.fault_count, u32::MAX);
+        for &data_rate in WAKE_UP_DATA_RATES.iter() {
+            let fault_count = fault_count_for(delay_ms, data_rate);
+            let achieved = u32::from(fault_count) * data_rate.period_ms();
+            let error = achieved.abs_diff(delay_ms);
+            if error < best.2 {
+                best = (data_rate, fault_count, error);
+            }
+        }
+        self.data_rate = best.0;
+        self.fault_count = best.1;
+        self.delay_ms()
+    }
+}
+
+fn fault_count_for(delay_ms: u32, data_rate: WakeUpOutputDataRate) -> u8 {
+    let period = data_rate.period_ms();
+    let count = delay_ms.saturating_add(period / 2) / period;
+    if count < 1 {
+        1
+    } else if count > 255 {
+        255
+    } else {
+        count as u8
+    }
+}
+
+impl<I2C, E, IC> Kxcj9<I2C, IC>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    IC: ScaledDevice,
+{
+    /// Typical start-up time in microseconds for the current output data
+    /// rate and resolution.
+    ///
+    /// See [`OutputDataRate::start_up_time_us()`](enum.OutputDataRate.html#method.start_up_time_us).
+    pub fn start_up_time_us(&self) -> u32 {
+        self.output_data_rate().start_up_time_us(self.resolution())
+    }
+
+    /// Enable the device and wait until the first valid measurement is available.
+    ///
+    /// The wait time depends on the configured output data rate and
+    /// resolution. See [`start_up_time_us()`](#method.start_up_time_us).
+    pub fn enable_and_wait<D: DelayUs<u32>>(&mut self, delay: &mut D) -> Result<(), Error<E>> {
+        self.enable()?;
+        delay.delay_us(self.start_up_time_us());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WakeUpOutputDataRate as ODR;
+
+    fn config(data_rate: ODR, fault_count: u8) -> WakeUpInterruptConfig {
+        WakeUpInterruptConfig {
+            data_rate,
+            fault_count,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn can_get_delay() {
+        assert_eq!(1280, config(ODR::Hz0_781, 1).delay_ms());
+        assert_eq!(2550, config(ODR::Hz100, 255).delay_ms());
+    }
+
+    #[test]
+    fn can_set_delay_for_data_rate() {
+        let mut config = config(ODR::Hz50, 1);
+        assert_eq!(500, config.set_delay_ms(500));
+        assert_eq!(25, config.fault_count);
+        assert_eq!(20, config.set_delay_ms(29));
+        assert_eq!(40, config.set_delay_ms(30));
+    }
+
+    #[test]
+    fn set_delay_limits_fault_count() {
+        let mut config = config(ODR::Hz100, 5);
+        assert_eq!(10, config.set_delay_ms(0));
+        assert_eq!(1, config.fault_count);
+        assert_eq!(2550, config.set_delay_ms(10_000));
+        assert_eq!(255, config.fault_count);
+    }
+
+    #[test]
+    fn fit_selects_lowest_exact_data_rate() {
+        let mut config = WakeUpInterruptConfig::default();
+        assert_eq!(1000, config.fit_delay_ms(1000));
+        assert_eq!(ODR::Hz25, config.data_rate);
+        assert_eq!(25, config.fault_count);
+    }
+
+    #[test]
+    fn fit_selects_closest_delay() {
+        let config = WakeUpInterruptConfig::from_delay_ms(12);
+        assert_eq!(ODR::Hz100, config.data_rate);
+        assert_eq!(10, config.delay_ms());
+        let config = WakeUpInterruptConfig::from_delay_ms(1_000_000);
+        assert_eq!(ODR::Hz0_781, config.data_rate);
+        assert_eq!(255 * 1280, config.delay_ms());
+    }
+}
@@ -0,0 +1,359 @@
+use crate::Kxcj9;
+use hal::blocking::i2c::{Write, WriteRead};
+
+/// Kind of register access
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessKind {
+    /// Register read
+    Read,
+    /// Register write
+    Write,
+}
+
+/// Register access reported to a trace hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterAccess<'a> {
+    /// Kind of access
+    pub kind: AccessKind,
+    /// Device address
+    pub address: u8,
+    /// First register accessed
+    pub register: u8,
+    /// Values written or read starting at the register
+    ///
+    /// For failed reads this contains the content of the read buffer.
+    pub data: &'a [u8],
+    /// Whether the I²C transaction succeeded
+    pub success: bool,
+}
+
+/// Hook called for every register access
+///
+/// See [`TracingI2c`](struct.TracingI2c.html).
+pub trait TraceHook {
+    /// Called after each register access.
+    fn trace(&mut self, access: &RegisterAccess<'_>);
+}
+
+impl<T: TraceHook + ?Sized> TraceHook for &mut T {
+    fn trace(&mut self, access: &RegisterAccess<'_>) {
+        (**self).trace(access)
+    }
+}
+
+/// I²C bus wrapper reporting every register access to a trace hook
+///
+/// See [`Kxcj9::with_trace_hook()`](struct.Kxcj9.html#method.with_trace_hook).
+#[derive(Debug)]
+pub struct TracingI2c<I2C, H> {
+    i2c: I2C,
+    hook: H,
+}
+
+impl<I2C, H> TracingI2c<I2C, H> {
+    /// Create a new wrapper.
+    pub fn new(i2c: I2C, hook: H) -> Self {
+        TracingI2c { i2c, hook }
+    }
+
+    /// Destroy the wrapper, return the I²C bus and hook instances.
+    pub fn destroy(self) -> (I2C, H) {
+        (self.i2c, self.hook)
+    }
+
+    /// Get a reference to the hook.
+    pub fn hook(&self) -> &H {
+        &self.hook
+    }
+
+    /// Get a mutable reference to the hook.
+    pub fn hook_mut(&mut self) -> &mut H {
+        &mut self.hook
+    }
+}
+
+impl<I2C: Write, H: TraceHook> Write for TracingI2c<I2C, H> {
+    type Error = I2C::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        let result = self.i2c.write(address, bytes);
+        if let Some((&register, data)) = bytes.split_first() {
+            self.hook.trace(&RegisterAccess {
+                kind: AccessKind::Write,
+                address,
+                register,
+                data,
+                success: result.is_ok(),
+            });
+        }
+        result
+    }
+}
+
+impl<I2C: WriteRead, H: TraceHook> WriteRead for TracingI2c<I2C, H> {
+    type Error = I2C::Error;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let result = self.i2c.write_read(address, bytes, buffer);
+        if let Some(&register) = bytes.first() {
+            self.hook.trace(&RegisterAccess {
+                kind: AccessKind::Read,
+                address,
+                register,
+                data: buffer,
+                success: result.is_ok(),
+            });
+        }
+        result
+    }
+}
+
+impl<I2C, IC> Kxcj9<I2C, IC> {
+    /// Report every register access to a trace hook.
+    ///
+    /// The hook can be retrieved with `sensor.i2c().hook()`.
+    pub fn with_trace_hook<H: TraceHook>(self, hook: H) -> Kxcj9<TracingI2c<I2C, H>, IC> {
+        let (sensor, i2c) = self.detach();
+        sensor.attach(TracingI2c::new(i2c, hook))
+    }
+}
+
+/// Trace hook logging every register access with the `log` crate
+/// at trace level (requires the `log` feature)
+#[cfg(feature = "log")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogHook;
+
+#[cfg(feature = "log")]
+impl TraceHook for LogHook {
+    fn trace(&mut self, access: &RegisterAccess<'_>) {
+        log::trace!(
+            "kxcj9 {:?} address: {:#04x} register: {:#04x} data: {:02x?} success: {}",
+            access.kind,
+            access.address,
+            access.register,
+            access.data,
+            access.success
+        );
+    }
+}
+
+/// Trace hook logging every register access with the `defmt` crate
+/// at trace level (requires the `defmt` feature)
+#[cfg(feature = "defmt")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefmtHook;
+
+#[cfg(feature = "defmt")]
+impl TraceHook for DefmtHook {
+    fn trace(&mut self, access: &RegisterAccess<'_>) {
+        let write = access.kind == AccessKind::Write;
+        defmt::trace!(
+            "kxcj9 write: {} address: {=u8:#x} register: {=u8:#x} data: {=[u8]:#x} success: {}",
+            write,
+            access.address,
+            access.register,
+            access.data,
+            access.success
+        );
+    }
+}
+
+/// Maximum number of data bytes stored per recorded access
+pub const RECORDED_DATA_LEN: usize = 6;
+
+/// Register access stored in a [`MemoryRecorder`](struct.MemoryRecorder.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordedAccess {
+    /// Kind of access
+    pub kind: AccessKind,
+    /// Device address
+    pub address: u8,
+    /// First register accessed
+    pub register: u8,
+    /// Whether the I²C transaction succeeded
+    pub success: bool,
+    data: [u8; RECORDED_DATA_LEN],
+    len: u8,
+}
+
+impl RecordedAccess {
+    const EMPTY: Self = RecordedAccess {
+        kind: AccessKind::Read,
+        address: 0,
+        register: 0,
+        success: false,
+        data: [0; RECORDED_DATA_LEN],
+        len: 0,
+    };
+
+    /// Values written or read starting at the register.
+    ///
+    /// Only the first [`RECORDED_DATA_LEN`](constant.RECORDED_DATA_LEN.html)
+    /// bytes are stored.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..usize::from(self.len)]
+    }
+}
+
+impl<'a> From<&RegisterAccess<'a>> for RecordedAccess {
+    fn from(access: &RegisterAccess<'a>) -> Self {
+        let len = access.data.len().min(RECORDED_DATA_LEN);
+        let mut data = [0; RECORDED_DATA_LEN];
+        data[..len].copy_from_slice(&access.data[..len]);
+        RecordedAccess {
+            kind: access.kind,
+            address: access.address,
+            register: access.register,
+            success: access.success,
+            data,
+            len: len as u8,
+        }
+    }
+}
+
+/// Trace hook storing the last `N` register accesses in memory
+#[derive(Debug, Clone)]
+pub struct MemoryRecorder<const N: usize> {
+    records: [RecordedAccess; N],
+    head: usize,
+    len: usize,
+    dropped: usize,
+}
+
+impl<const N: usize> Default for MemoryRecorder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> MemoryRecorder<N> {
+    /// Create a new empty recorder.
+    pub fn new() -> Self {
+        MemoryRecorder {
+            records: [RecordedAccess::EMPTY; N],
+            head: 0,
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Number of stored accesses.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no access is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of accesses which have been overwritten because the
+    /// recorder was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Stored accesses from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &RecordedAccess> {
+        let start = (self.head + N - self.len) % N.max(1);
+        (0..self.len).map(move |i| &self.records[(start + i) % N])
+    }
+
+    /// Remove all stored accesses.
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.dropped = 0;
+    }
+}
+
+impl<const N: usize> TraceHook for MemoryRecorder<N> {
+    fn trace(&mut self, access: &RegisterAccess<'_>) {
+        if N == 0 {
+            self.dropped += 1;
+            return;
+        }
+        self.records[self.head] = access.into();
+        self.head = (self.head + 1) % N;
+        if self.len < N {
+            self.len += 1;
+        } else {
+            self.dropped += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(register: u8, data: &[u8]) -> RegisterAccess<'_> {
+        RegisterAccess {
+            kind: AccessKind::Write,
+            address: 0x0E,
+            register,
+            data,
+            success: true,
+        }
+    }
+
+    fn registers<const N: usize>(recorder: &MemoryRecorder<N>) -> [u8; 4] {
+        let mut registers = [0; 4];
+        for (r, access) in registers.iter_mut().zip(recorder.iter()) {
+            *r = access.register;
+        }
+        registers
+    }
+
+    #[test]
+    fn records_accesses_in_order() {
+        let mut recorder = MemoryRecorder::<4>::new();
+        recorder.trace(&write(1, &[0]));
+        recorder.trace(&write(2, &[1]));
+        assert_eq!(2, recorder.len());
+        assert_eq!([1, 2, 0, 0], registers(&recorder));
+        assert_eq!(&[1], recorder.iter().nth(1).unwrap().data());
+    }
+
+    #[test]
+    fn overwrites_oldest_accesses() {
+        let mut recorder = MemoryRecorder::<3>::new();
+        for register in 1..6 {
+            recorder.trace(&write(register, &[0]));
+        }
+        assert_eq!(3, recorder.len());
+        assert_eq!(2, recorder.dropped());
+        assert_eq!([3, 4, 5, 0], registers(&recorder));
+        recorder.clear();
+        assert!(recorder.is_empty());
+    }
+
+    #[test]
+    fn truncates_long_data() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        let access = RegisterAccess {
+            kind: AccessKind::Read,
+            address: 0x0E,
+            register: 6,
+            data: &data,
+            success: true,
+        };
+        let recorded = RecordedAccess::from(&access);
+        assert_eq!(&data[..RECORDED_DATA_LEN], recorded.data());
+    }
+
+    #[test]
+    fn zero_capacity_drops_everything() {
+        let mut recorder = MemoryRecorder::<0>::new();
+        recorder.trace(&write(1, &[0]));
+        assert!(recorder.is_empty());
+        assert_eq!(1, recorder.dropped());
+        assert_eq!(0, recorder.iter().count());
+    }
+}
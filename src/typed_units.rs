@@ -0,0 +1,47 @@
+use crate::{i2c, Error, Kxcj9, ScaledDevice, WakeUpInterruptConfig};
+use uom::si::acceleration::{meter_per_second_squared, standard_gravity};
+use uom::si::f32::Acceleration;
+
+/// Acceleration measurement as typed quantities
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AccelerationMeasurement {
+    /// X-axis acceleration.
+    pub x: Acceleration,
+    /// Y-axis acceleration.
+    pub y: Acceleration,
+    /// Z-axis acceleration.
+    pub z: Acceleration,
+}
+
+impl<I2C, E, IC> Kxcj9<I2C, IC>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    IC: ScaledDevice,
+{
+    /// Read acceleration sensor data as typed acceleration quantities.
+    ///
+    /// The conversion uses the configured standard gravity value.
+    /// See [`set_standard_gravity()`](#method.set_standard_gravity).
+    pub fn read_acceleration(&mut self) -> Result<AccelerationMeasurement, Error<E>> {
+        let m = self.read_m_s2()?;
+        Ok(AccelerationMeasurement {
+            x: Acceleration::new::<meter_per_second_squared>(m.x),
+            y: Acceleration::new::<meter_per_second_squared>(m.y),
+            z: Acceleration::new::<meter_per_second_squared>(m.z),
+        })
+    }
+}
+
+impl WakeUpInterruptConfig {
+    /// Get the wake-up acceleration change threshold as a typed quantity.
+    pub fn threshold_acceleration(&self) -> Acceleration {
+        Acceleration::new::<standard_gravity>(self.threshold)
+    }
+
+    /// Set the wake-up acceleration change threshold from a typed quantity.
+    ///
+    /// The value is converted to G using the standard acceleration of gravity.
+    pub fn set_threshold_acceleration(&mut self, threshold: Acceleration) {
+        self.threshold = threshold.get::<standard_gravity>();
+    }
+}
@@ -1,16 +1,99 @@
+use core::fmt;
+
 /// All possible errors in this crate
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Error<E> {
     /// I²C bus error
     I2C(E),
     /// Invalid setting for the current configuration
-    InvalidSetting,
-    /// Error occured during self-test
-    SelfTestError,
+    InvalidSetting(SettingError),
+    /// Error occured during the digital communication self-test
+    SelfTestError(SelfTestStage),
+    /// The `WHO_AM_I` register value does not match the device
+    WrongDevice {
+        /// Value read from the `WHO_AM_I` register
+        who_am_i: u8,
+    },
+    /// The software reset did not finish in time
+    ResetTimeout,
+    /// No new measurement was available in time
+    ReadTimeout,
+}
+
+/// Reason why a setting is invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SettingError {
+    /// The wake-up interrupt fault count is zero
+    ZeroFaultCount,
+    /// The wake-up threshold is outside of the range supported by the device
+    ThresholdOutOfRange,
+    /// Low resolution is not available at output data rates of 400 Hz or higher
+    LowResolutionAtHighDataRate,
+    /// The requested range is larger than the largest range of the device
+    RangeOutOfRange,
+}
+
+/// Stage at which the digital communication self-test failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfTestStage {
+    /// `DCST_RESP` did not contain `0x55` before starting the test
+    InitialResponse,
+    /// `DCST_RESP` did not contain `0xAA` after starting the test
+    TestResponse,
+    /// The `DCST` bit in `CTRL2` was not cleared after reading `DCST_RESP`
+    DcstNotCleared,
+    /// `DCST_RESP` did not contain `0x55` after finishing the test
+    FinalResponse,
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::I2C(e) => write!(f, "I2C bus error: {:?}", e),
+            Error::InvalidSetting(reason) => write!(f, "invalid setting: {}", reason),
+            Error::SelfTestError(stage) => write!(f, "self-test failed: {}", stage),
+            Error::WrongDevice { who_am_i } => {
+                write!(f, "unexpected WHO_AM_I value: {:#04x}", who_am_i)
+            }
+            Error::ResetTimeout => write!(f, "software reset timed out"),
+            Error::ReadTimeout => write!(f, "no new measurement available in time"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug> std::error::Error for Error<E> {}
+
+impl fmt::Display for SettingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingError::ZeroFaultCount => write!(f, "wake-up fault count must be at least 1"),
+            SettingError::ThresholdOutOfRange => {
+                write!(f, "wake-up threshold out of range for the device")
+            }
+            SettingError::LowResolutionAtHighDataRate => write!(
+                f,
+                "low resolution is not available at output data rates of 400 Hz or higher"
+            ),
+            SettingError::RangeOutOfRange => write!(f, "range not supported by the device"),
+        }
+    }
+}
+
+impl fmt::Display for SelfTestStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SelfTestStage::InitialResponse => write!(f, "initial response was not 0x55"),
+            SelfTestStage::TestResponse => write!(f, "test response was not 0xAA"),
+            SelfTestStage::DcstNotCleared => write!(f, "DCST bit was not cleared"),
+            SelfTestStage::FinalResponse => write!(f, "final response was not 0x55"),
+        }
+    }
 }
 
 /// Measurement resolution
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Resolution {
     /// 8-bit resolution.
     Low,
@@ -19,7 +102,10 @@ pub enum Resolution {
 }
 
 /// KXCJ9-1008 G scale (up to +/-8g)
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// This is the scale used by `set_scale()` for KXCJ9-1008 and KXCJB-1041
+/// devices (`Kxcj9<I2C, ic::G8Device>`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GScale8 {
     /// Range: +/-2g
     G2,
@@ -32,7 +118,10 @@ pub enum GScale8 {
 }
 
 /// KXCJ9-1018 G scale (up to +/-16g)
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// This is the scale used by `set_scale()` for KXCJ9-1018 devices
+/// (`Kxcj9<I2C, ic::G16Device>`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GScale16 {
     /// Range: +/-4g
     G4,
@@ -45,7 +134,7 @@ pub enum GScale16 {
 }
 
 /// Output data rate
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputDataRate {
     /// 0.781 Hz
     Hz0_781,
@@ -74,18 +163,28 @@ pub enum OutputDataRate {
 }
 
 /// Acceleration measurement scaled to configured G range
-#[derive(Debug, Default, Clone)]
+///
+/// The unit depends on the method that returned it: `read()` returns
+/// values in g and `read_m_s2()` returns values in m/s².
+///
+/// With the `mint`, `nalgebra` or `micromath` features enabled, it converts
+/// to and from their `f32` 3D vector types.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Measurement {
-    /// X-axis acceleration.
+    /// X-axis acceleration in g or m/s².
     pub x: f32,
-    /// Y-axis acceleration.
+    /// Y-axis acceleration in g or m/s².
     pub y: f32,
-    /// Z-axis acceleration.
+    /// Z-axis acceleration in g or m/s².
     pub z: f32,
 }
 
 /// Unscaled acceleration measurement
-#[derive(Debug, Default, Clone, PartialEq)]
+///
+/// Arithmetic operators saturate component-wise instead of overflowing.
+/// With the `mint`, `nalgebra` or `micromath` features enabled, it converts
+/// to and from their `i16` 3D vector types.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct UnscaledMeasurement {
     /// X-axis acceleration.
     pub x: i16,
@@ -96,7 +195,7 @@ pub struct UnscaledMeasurement {
 }
 
 /// Interrupt source information
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct InterruptInfo {
     /// New acceleration data is available
     pub data_ready: bool,
@@ -116,6 +215,40 @@ pub struct InterruptInfo {
     pub wake_up_z_positive: bool,
 }
 
+/// Acceleration axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// X-axis
+    X,
+    /// Y-axis
+    Y,
+    /// Z-axis
+    Z,
+}
+
+/// Motion direction along an axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Negative direction
+    Negative,
+    /// Positive direction
+    Positive,
+}
+
+/// Acceleration event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// New acceleration data is available
+    DataReady,
+    /// Wake-up motion detected
+    Motion {
+        /// Axis along which the motion was detected
+        axis: Axis,
+        /// Direction of the detected motion
+        direction: Direction,
+    },
+}
+
 /// Wake-up interrupt configuration
 #[derive(Debug, Clone, Copy)]
 pub struct WakeUpInterruptConfig {
@@ -126,8 +259,10 @@ pub struct WakeUpInterruptConfig {
     /// Number of faults necessary to trigger a wake-up interrupt.
     ///
     /// Each count accounts for a delay of `1/data_rate`.
+    /// See [`set_delay_ms()`](#method.set_delay_ms) for setting it from
+    /// a delay in milliseconds.
     /// The minimum value is 1. Configuring with `fault_count = 0`
-    /// will return an `Error::InvalidSetting`.
+    /// will return an `Error::InvalidSetting(SettingError::ZeroFaultCount)`.
     pub fault_count: u8,
     /// Wake-up acceleration change threshold in G.
     ///
@@ -180,7 +315,7 @@ impl Default for WakeUpTriggerMotion {
 }
 
 /// Output data rate for wake-up motion detection
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WakeUpOutputDataRate {
     /// 0.781 Hz (default)
     Hz0_781,
@@ -200,6 +335,7 @@ pub enum WakeUpOutputDataRate {
     Hz100,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for WakeUpOutputDataRate {
     fn default() -> Self {
         WakeUpOutputDataRate::Hz0_781
@@ -207,7 +343,7 @@ impl Default for WakeUpOutputDataRate {
 }
 
 /// Physical interrupt pin polarity
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InterruptPinPolarity {
     /// Low state when active
     ActiveLow,
@@ -216,7 +352,7 @@ pub enum InterruptPinPolarity {
 }
 
 /// Physical interrupt pin latching behavior
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InterruptPinLatching {
     /// Interrupt pin will transmit a single pulse with a period of `0.03ms - 0.05ms`
     /// when triggered, but only once. No further pulses will be transmitted until
@@ -226,8 +362,45 @@ pub enum InterruptPinLatching {
     Latching,
 }
 
+/// Device variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum DeviceVariant {
+    /// KXCJ9-1008
+    Kxcj9_1008,
+    /// KXCJ9-1018
+    Kxcj9_1018,
+    /// KXCJB-1041
+    Kxcjb_1041,
+}
+
+const DEVICE_VARIANTS: [DeviceVariant; 3] = [
+    DeviceVariant::Kxcj9_1008,
+    DeviceVariant::Kxcj9_1018,
+    DeviceVariant::Kxcjb_1041,
+];
+
+impl DeviceVariant {
+    /// Variant corresponding to a `WHO_AM_I` register value.
+    pub fn from_who_am_i(who_am_i: u8) -> Option<Self> {
+        DEVICE_VARIANTS
+            .iter()
+            .cloned()
+            .find(|variant| variant.who_am_i() == who_am_i)
+    }
+
+    /// `WHO_AM_I` register value of the variant.
+    pub const fn who_am_i(self) -> u8 {
+        match self {
+            DeviceVariant::Kxcj9_1008 => 0x0A,
+            DeviceVariant::Kxcj9_1018 => 0x1D,
+            DeviceVariant::Kxcjb_1041 => 0x21,
+        }
+    }
+}
+
 /// Possible slave addresses
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SlaveAddr {
     /// Default slave address
     Default,
@@ -254,7 +427,7 @@ impl SlaveAddr {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use DEVICE_BASE_ADDRESS as BASE_ADDR;
+    use crate::DEVICE_BASE_ADDRESS as BASE_ADDR;
 
     #[test]
     fn can_get_default_address() {
@@ -267,4 +440,19 @@ mod tests {
         assert_eq!(0b000_1110, SlaveAddr::Alternative(false).addr(BASE_ADDR));
         assert_eq!(0b000_1111, SlaveAddr::Alternative(true).addr(BASE_ADDR));
     }
+
+    #[test]
+    fn can_identify_variant_from_who_am_i() {
+        for variant in [
+            DeviceVariant::Kxcj9_1008,
+            DeviceVariant::Kxcj9_1018,
+            DeviceVariant::Kxcjb_1041,
+        ] {
+            assert_eq!(
+                Some(variant),
+                DeviceVariant::from_who_am_i(variant.who_am_i())
+            );
+        }
+        assert_eq!(None, DeviceVariant::from_who_am_i(0xAB));
+    }
 }
@@ -0,0 +1,439 @@
+use crate::{Measurement, UnscaledMeasurement};
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use libm::{acosf, sqrtf};
+
+impl Measurement {
+    /// Euclidean norm of the acceleration vector.
+    pub fn magnitude(&self) -> f32 {
+        sqrtf(self.dot(self))
+    }
+
+    /// Dot product with another measurement.
+    pub fn dot(&self, other: &Measurement) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Unit vector pointing in the same direction.
+    ///
+    /// A zero measurement is returned unchanged.
+    pub fn normalize(&self) -> Measurement {
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            *self
+        } else {
+            *self / magnitude
+        }
+    }
+
+    /// Angle between this and another measurement in radians (`0..=π`).
+    ///
+    /// Returns `NaN` if any of the measurements is zero.
+    pub fn angle_between(&self, other: &Measurement) -> f32 {
+        let cos = self.dot(other) / (self.magnitude() * other.magnitude());
+        acosf(cos.clamp(-1.0, 1.0))
+    }
+}
+
+impl Add for Measurement {
+    type Output = Measurement;
+
+    fn add(self, other: Measurement) -> Measurement {
+        Measurement {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl Sub for Measurement {
+    type Output = Measurement;
+
+    fn sub(self, other: Measurement) -> Measurement {
+        Measurement {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl Neg for Measurement {
+    type Output = Measurement;
+
+    fn neg(self) -> Measurement {
+        Measurement {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl Mul<f32> for Measurement {
+    type Output = Measurement;
+
+    fn mul(self, factor: f32) -> Measurement {
+        Measurement {
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+        }
+    }
+}
+
+impl Div<f32> for Measurement {
+    type Output = Measurement;
+
+    fn div(self, divisor: f32) -> Measurement {
+        Measurement {
+            x: self.x / divisor,
+            y: self.y / divisor,
+            z: self.z / divisor,
+        }
+    }
+}
+
+impl UnscaledMeasurement {
+    /// Euclidean norm of the acceleration vector in LSB.
+    pub fn magnitude(&self) -> f32 {
+        self.to_measurement_lsb().magnitude()
+    }
+
+    /// Dot product with another measurement.
+    pub fn dot(&self, other: &UnscaledMeasurement) -> i64 {
+        i64::from(self.x) * i64::from(other.x)
+            + i64::from(self.y) * i64::from(other.y)
+            + i64::from(self.z) * i64::from(other.z)
+    }
+
+    /// Raw LSB values as a measurement without applying any scale.
+    pub fn to_measurement_lsb(&self) -> Measurement {
+        Measurement {
+            x: f32::from(self.x),
+            y: f32::from(self.y),
+            z: f32::from(self.z),
+        }
+    }
+
+    /// Unit vector pointing in the same direction.
+    ///
+    /// A zero measurement returns a zero vector.
+    pub fn normalize(&self) -> Measurement {
+        self.to_measurement_lsb().normalize()
+    }
+
+    /// Angle between this and another measurement in radians (`0..=π`).
+    ///
+    /// Returns `NaN` if any of the measurements is zero.
+    pub fn angle_between(&self, other: &UnscaledMeasurement) -> f32 {
+        self.to_measurement_lsb()
+            .angle_between(&other.to_measurement_lsb())
+    }
+}
+
+impl Add for UnscaledMeasurement {
+    type Output = UnscaledMeasurement;
+
+    /// Component-wise saturating addition.
+    fn add(self, other: UnscaledMeasurement) -> UnscaledMeasurement {
+        UnscaledMeasurement {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y),
+            z: self.z.saturating_add(other.z),
+        }
+    }
+}
+
+impl Sub for UnscaledMeasurement {
+    type Output = UnscaledMeasurement;
+
+    /// Component-wise saturating subtraction.
+    fn sub(self, other: UnscaledMeasurement) -> UnscaledMeasurement {
+        UnscaledMeasurement {
+            x: self.x.saturating_sub(other.x),
+            y: self.y.saturating_sub(other.y),
+            z: self.z.saturating_sub(other.z),
+        }
+    }
+}
+
+impl Neg for UnscaledMeasurement {
+    type Output = UnscaledMeasurement;
+
+    /// Component-wise saturating negation.
+    fn neg(self) -> UnscaledMeasurement {
+        UnscaledMeasurement {
+            x: self.x.saturating_neg(),
+            y: self.y.saturating_neg(),
+            z: self.z.saturating_neg(),
+        }
+    }
+}
+
+impl Mul<i16> for UnscaledMeasurement {
+    type Output = UnscaledMeasurement;
+
+    /// Component-wise saturating scaling.
+    fn mul(self, factor: i16) -> UnscaledMeasurement {
+        UnscaledMeasurement {
+            x: self.x.saturating_mul(factor),
+            y: self.y.saturating_mul(factor),
+            z: self.z.saturating_mul(factor),
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+mod mint_conversion {
+    use crate::{Measurement, UnscaledMeasurement};
+    use mint::Vector3;
+
+    impl From<Measurement> for Vector3<f32> {
+        fn from(m: Measurement) -> Self {
+            Vector3 {
+                x: m.x,
+                y: m.y,
+                z: m.z,
+            }
+        }
+    }
+
+    impl From<Vector3<f32>> for Measurement {
+        fn from(v: Vector3<f32>) -> Self {
+            Measurement {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+            }
+        }
+    }
+
+    impl From<UnscaledMeasurement> for Vector3<i16> {
+        fn from(m: UnscaledMeasurement) -> Self {
+            Vector3 {
+                x: m.x,
+                y: m.y,
+                z: m.z,
+            }
+        }
+    }
+
+    impl From<Vector3<i16>> for UnscaledMeasurement {
+        fn from(v: Vector3<i16>) -> Self {
+            UnscaledMeasurement {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_conversion {
+    use crate::{Measurement, UnscaledMeasurement};
+    use nalgebra::Vector3;
+
+    impl From<Measurement> for Vector3<f32> {
+        fn from(m: Measurement) -> Self {
+            Vector3::new(m.x, m.y, m.z)
+        }
+    }
+
+    impl From<Vector3<f32>> for Measurement {
+        fn from(v: Vector3<f32>) -> Self {
+            Measurement {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+            }
+        }
+    }
+
+    impl From<UnscaledMeasurement> for Vector3<i16> {
+        fn from(m: UnscaledMeasurement) -> Self {
+            Vector3::new(m.x, m.y, m.z)
+        }
+    }
+
+    impl From<Vector3<i16>> for UnscaledMeasurement {
+        fn from(v: Vector3<i16>) -> Self {
+            UnscaledMeasurement {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "micromath")]
+mod micromath_conversion {
+    use crate::{Measurement, UnscaledMeasurement};
+    use micromath::vector::{F32x3, I16x3};
+
+    impl From<Measurement> for F32x3 {
+        fn from(m: Measurement) -> Self {
+            F32x3 {
+                x: m.x,
+                y: m.y,
+                z: m.z,
+            }
+        }
+    }
+
+    impl From<F32x3> for Measurement {
+        fn from(v: F32x3) -> Self {
+            Measurement {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+            }
+        }
+    }
+
+    impl From<UnscaledMeasurement> for I16x3 {
+        fn from(m: UnscaledMeasurement) -> Self {
+            I16x3 {
+                x: m.x,
+                y: m.y,
+                z: m.z,
+            }
+        }
+    }
+
+    impl From<I16x3> for UnscaledMeasurement {
+        fn from(v: I16x3) -> Self {
+            UnscaledMeasurement {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::{FRAC_PI_2, PI};
+
+    fn assert_near(a: f32, b: f32) {
+        if (a - b) > 0.001 || (b - a) > 0.001 {
+            panic!();
+        }
+    }
+
+    fn m(x: f32, y: f32, z: f32) -> Measurement {
+        Measurement { x, y, z }
+    }
+
+    fn um(x: i16, y: i16, z: i16) -> UnscaledMeasurement {
+        UnscaledMeasurement { x, y, z }
+    }
+
+    #[test]
+    fn can_add_and_subtract() {
+        assert_eq!(m(1.5, 0.0, -1.0), m(1.0, 1.0, -2.0) + m(0.5, -1.0, 1.0));
+        assert_eq!(m(0.5, 2.0, -3.0), m(1.0, 1.0, -2.0) - m(0.5, -1.0, 1.0));
+        assert_eq!(um(3, 0, -1), um(1, 1, -2) + um(2, -1, 1));
+        assert_eq!(um(-1, 2, -3), um(1, 1, -2) - um(2, -1, 1));
+    }
+
+    #[test]
+    fn unscaled_operations_saturate() {
+        assert_eq!(um(i16::MAX, 0, 0), um(i16::MAX, 0, 0) + um(1, 0, 0));
+        assert_eq!(um(i16::MIN, 0, 0), um(i16::MIN, 0, 0) - um(1, 0, 0));
+        assert_eq!(um(i16::MAX, 0, 0), -um(i16::MIN, 0, 0));
+        assert_eq!(um(i16::MAX, i16::MIN, 4), um(20000, -20000, 2) * 2);
+    }
+
+    #[test]
+    fn can_scale() {
+        assert_eq!(m(2.0, -4.0, 1.0), m(1.0, -2.0, 0.5) * 2.0);
+        assert_eq!(m(0.5, -1.0, 0.25), m(1.0, -2.0, 0.5) / 2.0);
+        assert_eq!(m(-1.0, 2.0, -0.5), -m(1.0, -2.0, 0.5));
+    }
+
+    #[test]
+    fn can_calculate_magnitude() {
+        assert_near(5.0, m(3.0, 4.0, 0.0).magnitude());
+        assert_near(3.0, m(1.0, -2.0, 2.0).magnitude());
+        assert_near(5.0, um(0, -3, 4).magnitude());
+    }
+
+    #[test]
+    fn can_calculate_dot_product() {
+        assert_near(-1.0, m(1.0, 2.0, 3.0).dot(&m(2.0, 0.0, -1.0)));
+        assert_eq!(-1, um(1, 2, 3).dot(&um(2, 0, -1)));
+        assert_eq!(
+            3 * 8192 * 8192,
+            um(8192, 8192, 8192).dot(&um(8192, 8192, 8192))
+        );
+        assert_eq!(
+            3 * i64::from(i16::MIN) * i64::from(i16::MIN),
+            um(i16::MIN, i16::MIN, i16::MIN).dot(&um(i16::MIN, i16::MIN, i16::MIN))
+        );
+    }
+
+    #[test]
+    fn magnitude_does_not_overflow_near_limits() {
+        let expected = 30000.0 * sqrtf(3.0);
+        let actual = um(30000, 30000, 30000).magnitude();
+        assert!((expected - actual).abs() < 1.0);
+        let expected = 32768.0 * sqrtf(3.0);
+        let actual = um(i16::MIN, i16::MIN, i16::MIN).magnitude();
+        assert!((expected - actual).abs() < 1.0);
+    }
+
+    #[test]
+    fn can_normalize() {
+        let n = m(0.0, 3.0, -4.0).normalize();
+        assert_near(0.0, n.x);
+        assert_near(0.6, n.y);
+        assert_near(-0.8, n.z);
+        assert_near(1.0, um(10, -20, 30).normalize().magnitude());
+        assert_eq!(m(0.0, 0.0, 0.0), m(0.0, 0.0, 0.0).normalize());
+    }
+
+    #[test]
+    fn can_calculate_angle_between() {
+        assert_near(0.0, m(0.0, 0.0, 1.0).angle_between(&m(0.0, 0.0, 2.0)));
+        assert_near(FRAC_PI_2, m(1.0, 0.0, 0.0).angle_between(&m(0.0, 1.0, 0.0)));
+        assert_near(PI, um(0, 0, 64).angle_between(&um(0, 0, -64)));
+        assert!(m(0.0, 0.0, 0.0).angle_between(&m(1.0, 0.0, 0.0)).is_nan());
+    }
+
+    #[test]
+    fn can_convert_unscaled() {
+        assert_eq!(m(1.0, -2.0, 3.0), um(1, -2, 3).to_measurement_lsb());
+    }
+
+    #[cfg(feature = "mint")]
+    #[test]
+    fn can_convert_mint() {
+        let v: mint::Vector3<f32> = m(1.0, -2.0, 3.0).into();
+        assert_eq!(m(1.0, -2.0, 3.0), Measurement::from(v));
+        let v: mint::Vector3<i16> = um(1, -2, 3).into();
+        assert_eq!(um(1, -2, 3), UnscaledMeasurement::from(v));
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn can_convert_nalgebra() {
+        let v: nalgebra::Vector3<f32> = m(1.0, -2.0, 3.0).into();
+        assert_eq!(m(1.0, -2.0, 3.0), Measurement::from(v));
+        let v: nalgebra::Vector3<i16> = um(1, -2, 3).into();
+        assert_eq!(um(1, -2, 3), UnscaledMeasurement::from(v));
+    }
+
+    #[cfg(feature = "micromath")]
+    #[test]
+    fn can_convert_micromath() {
+        let v: micromath::vector::F32x3 = m(1.0, -2.0, 3.0).into();
+        assert_eq!(m(1.0, -2.0, 3.0), Measurement::from(v));
+        let v: micromath::vector::I16x3 = um(1, -2, 3).into();
+        assert_eq!(um(1, -2, 3), UnscaledMeasurement::from(v));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{i2c, Error, Kxcj9, OutputDataRate, Resolution, ScaledDevice, WakeUpInterruptConfig};
+
+/// Power state of the wake-on-motion state machine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerState {
+    /// Low output data rate waiting for wake-up motion.
+    Sleep,
+    /// High output data rate after motion was detected.
+    Active,
+}
+
+/// Wake-on-motion configuration
+#[derive(Debug, Clone, Copy)]
+pub struct WakeOnMotionConfig {
+    /// Wake-up interrupt configuration used to detect motion.
+    pub wake_up: WakeUpInterruptConfig,
+    /// Output data rate while sleeping.
+    pub sleep_data_rate: OutputDataRate,
+    /// Output data rate while active.
+    pub active_data_rate: OutputDataRate,
+    /// Time without wake-up motion after which the device goes back
+    /// to sleep in milliseconds.
+    pub inactivity_timeout_ms: u32,
+}
+
+impl Default for WakeOnMotionConfig {
+    fn default() -> Self {
+        WakeOnMotionConfig {
+            wake_up: WakeUpInterruptConfig::default(),
+            sleep_data_rate: OutputDataRate::Hz0_781,
+            active_data_rate: OutputDataRate::Hz100,
+            inactivity_timeout_ms: 5000,
+        }
+    }
+}
+
+/// Wake-on-motion sleep/active power state machine
+///
+/// While sleeping the device runs at a low output data rate with the
+/// wake-up interrupt armed. Once wake-up motion is detected the device
+/// switches to the active output data rate. The wake-up interrupt stays
+/// armed while active so that any further motion restarts the inactivity
+/// timeout. After the inactivity timeout elapses without motion the device
+/// goes back to sleep.
+///
+/// An active output data rate of 400 Hz or more forces high resolution.
+/// The resolution configured when calling [`start()`] is restored when
+/// going back to sleep.
+///
+/// The state machine does not own the driver. Call [`start()`] once and
+/// then [`update()`] periodically with the time elapsed since the
+/// previous call.
+///
+/// [`start()`]: #method.start
+/// [`update()`]: #method.update
+#[derive(Debug, Clone)]
+pub struct WakeOnMotion {
+    config: WakeOnMotionConfig,
+    state: PowerState,
+    idle_ms: u32,
+    resolution: Resolution,
+}
+
+impl WakeOnMotion {
+    /// Create a new state machine. The device is not configured until
+    /// [`start()`](#method.start) is called.
+    pub fn new(config: WakeOnMotionConfig) -> Self {
+        WakeOnMotion {
+            config,
+            state: PowerState::Sleep,
+            idle_ms: 0,
+            resolution: Resolution::Low,
+        }
+    }
+
+    /// Current power state.
+    pub fn state(&self) -> PowerState {
+        self.state
+    }
+
+    /// Time elapsed without wake-up motion while active in milliseconds.
+    pub fn idle_ms(&self) -> u32 {
+        self.idle_ms
+    }
+
+    /// Configure the device, arm the wake-up interrupt and enable the
+    /// device in the sleep state.
+    pub fn start<I2C, E, IC>(&mut self, sensor: &mut Kxcj9<I2C, IC>) -> Result<(), Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+    {
+        sensor.set_output_data_rate(self.config.sleep_data_rate)?;
+        sensor.enable_wake_up_interrupt(self.config.wake_up)?;
+        sensor.clear_interrupts()?;
+        sensor.enable()?;
+        self.state = PowerState::Sleep;
+        self.idle_ms = 0;
+        self.resolution = sensor.resolution();
+        Ok(())
+    }
+
+    /// Check for wake-up motion and perform any necessary transition.
+    ///
+    /// `elapsed_ms` is the time elapsed since the previous call.
+    /// Returns the new power state if a transition happened.
+    pub fn update<I2C, E, IC>(
+        &mut self,
+        sensor: &mut Kxcj9<I2C, IC>,
+        elapsed_ms: u32,
+    ) -> Result<Option<PowerState>, Error<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        IC: ScaledDevice,
+    {
+        let info = sensor.read_interrupt_info()?;
+        if info.wake_up {
+            sensor.clear_interrupts()?;
+            self.idle_ms = 0;
+            if self.state == PowerState::Sleep {
+                sensor.set_output_data_rate(self.config.active_data_rate)?;
+                return Ok(Some(self.transition(PowerState::Active)));
+            }
+        } else if self.state == PowerState::Active {
+            self.idle_ms = self.idle_ms.saturating_add(elapsed_ms);
+            if self.idle_ms >= self.config.inactivity_timeout_ms {
+                sensor.set_output_data_rate(self.config.sleep_data_rate)?;
+                if sensor.resolution() != self.resolution {
+                    sensor.set_resolution(self.resolution)?;
+                }
+                self.idle_ms = 0;
+                return Ok(Some(self.transition(PowerState::Sleep)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn transition(&mut self, state: PowerState) -> PowerState {
+        self.state = state;
+        state
+    }
+}
@@ -0,0 +1,137 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use hal::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use hal::MockError;
+use kxcj9::{AnyKxcj9, Capabilities, DeviceVariant, Error, SlaveAddr, WakeUpInterruptConfig};
+use std::io::ErrorKind;
+
+mod common;
+use common::{BitFlags as BF, Register as Reg, DEV_ADDR};
+
+fn new(transactions: &[I2cTrans], variant: DeviceVariant) -> AnyKxcj9<I2cMock> {
+    AnyKxcj9::new(I2cMock::new(transactions), SlaveAddr::default(), variant)
+}
+
+fn destroy(sensor: AnyKxcj9<I2cMock>) {
+    sensor.destroy().done();
+}
+
+fn read_64(variant: DeviceVariant) -> f32 {
+    let transactions = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Reg::XOUT_L],
+        vec![64, 0, 0, 0, 0, 0],
+    )];
+    let mut sensor = new(&transactions, variant);
+    let m = sensor.read().unwrap();
+    destroy(sensor);
+    m.x
+}
+
+#[test]
+fn scales_according_to_variant() {
+    assert_eq!(1.0, read_64(DeviceVariant::Kxcj9_1008));
+    assert_eq!(1.0, read_64(DeviceVariant::Kxcjb_1041));
+    assert_eq!(2.0, read_64(DeviceVariant::Kxcj9_1018));
+}
+
+#[test]
+fn can_detect_variant() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::WHO_AM_I], vec![0x1D]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::WHO_AM_I], vec![0x1D]),
+    ];
+    let mut sensor = AnyKxcj9::detect(I2cMock::new(&transactions), SlaveAddr::default())
+        .map_err(|(e, _)| e)
+        .unwrap();
+    assert_eq!(DeviceVariant::Kxcj9_1018, sensor.variant());
+    assert_eq!(Capabilities::KXCJ9_1018, sensor.capabilities());
+    assert!(sensor.as_g16().is_some());
+    assert!(sensor.as_g8().is_none());
+    assert!(sensor.as_g16_mut().is_some());
+    assert!(sensor.as_g8_mut().is_none());
+    sensor.verify_who_am_i().unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn verifies_who_am_i_of_variant() {
+    let transactions = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Reg::WHO_AM_I],
+        vec![0x21],
+    )];
+    let mut sensor = new(&transactions, DeviceVariant::Kxcj9_1008);
+    assert_eq!(
+        Err(Error::WrongDevice { who_am_i: 0x21 }),
+        sensor.verify_who_am_i()
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn detect_returns_bus_for_unknown_device() {
+    let transactions = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Reg::WHO_AM_I],
+        vec![0xAB],
+    )];
+    match AnyKxcj9::detect(I2cMock::new(&transactions), SlaveAddr::default()) {
+        Err((Error::WrongDevice { who_am_i: 0xAB }, mut i2c)) => i2c.done(),
+        _ => panic!("Wrong result."),
+    }
+}
+
+#[test]
+fn detect_returns_bus_on_error() {
+    let transactions = [I2cTrans::write_read(DEV_ADDR, vec![Reg::WHO_AM_I], vec![0])
+        .with_error(MockError::Io(ErrorKind::Other))];
+    match AnyKxcj9::detect(I2cMock::new(&transactions), SlaveAddr::default()) {
+        Err((Error::I2C(_), mut i2c)) => i2c.done(),
+        _ => panic!("Wrong result."),
+    }
+}
+
+fn enable_wake_up_interrupt(variant: DeviceVariant, expected_threshold: u8) {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL2, 0b0011_1111]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_TIMER, 1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_THRESHOLD, expected_threshold]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE]),
+    ];
+    let mut sensor = new(&transactions, variant);
+    sensor
+        .enable_wake_up_interrupt(WakeUpInterruptConfig::default())
+        .unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn configures_interrupts_according_to_variant() {
+    // Default threshold of 0.5g
+    enable_wake_up_interrupt(DeviceVariant::Kxcj9_1008, 8);
+    enable_wake_up_interrupt(DeviceVariant::Kxcj9_1018, 4);
+}
+
+#[test]
+fn can_set_range_and_use_variant_specific_methods() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::GSEL0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::GSEL0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::GSEL1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::GSEL1 | BF::PC1]),
+    ];
+    let mut sensor = new(&transactions, DeviceVariant::Kxcj9_1008);
+    assert_eq!(4.0, sensor.set_range_at_least(3.0).unwrap());
+    sensor
+        .as_g8_mut()
+        .unwrap()
+        .set_scale(kxcj9::GScale8::G8)
+        .unwrap();
+    assert_eq!(8.0, sensor.range_g());
+    sensor.enable().unwrap();
+    destroy(sensor);
+}
@@ -0,0 +1,160 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+extern crate nb;
+use hal::delay::MockNoop;
+use hal::i2c::Transaction as I2cTrans;
+use hal::MockError;
+use kxcj9::{
+    Axis, BurstCapture, BurstCaptureConfig, Direction, Error, InterruptInfo, OutputDataRate,
+    UnscaledMeasurement,
+};
+use std::io::ErrorKind;
+
+mod common;
+use common::{destroy, new_1008, BitFlags as BF, Register as Reg, DEV_ADDR};
+
+const IDLE_ODR: u8 = 0b000_1000;
+const BURST_ODR: u8 = 0b000_0110;
+
+fn new_capture() -> BurstCapture {
+    BurstCapture::new(BurstCaptureConfig {
+        idle_data_rate: OutputDataRate::Hz0_781,
+        burst_data_rate: OutputDataRate::Hz800,
+        ..Default::default()
+    })
+}
+
+fn arm_transactions() -> Vec<I2cTrans> {
+    vec![
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::DATA_CTRL, IDLE_ODR]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL2, 0b0011_1111]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_TIMER, 1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_THRESHOLD, 8]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_REL], vec![0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::WUFE]),
+    ]
+}
+
+#[test]
+fn can_arm() {
+    let mut sensor = new_1008(&arm_transactions());
+    new_capture().arm(&mut sensor).unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn waits_for_motion() {
+    let mut transactions = arm_transactions();
+    transactions.push(I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Reg::INT_SOURCE1],
+        vec![0, 0],
+    ));
+    let mut sensor = new_1008(&transactions);
+    let mut capture = new_capture();
+    capture.arm(&mut sensor).unwrap();
+    let mut buffer = [UnscaledMeasurement::default(); 2];
+    assert_eq!(
+        Err(nb::Error::WouldBlock),
+        capture.capture(&mut sensor, &mut MockNoop::new(), &mut buffer)
+    );
+    destroy(sensor);
+}
+
+fn burst_start_transactions() -> Vec<I2cTrans> {
+    let mut transactions = arm_transactions();
+    transactions.extend_from_slice(&[
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![BF::WUFS, BF::XNWU]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::WUFE | BF::DRDYE]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE | BF::DRDYE]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::DATA_CTRL, BURST_ODR]),
+        I2cTrans::write(
+            DEV_ADDR,
+            vec![Reg::CTRL1, BF::PC1 | BF::WUFE | BF::DRDYE | BF::RES],
+        ),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_REL], vec![0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![BF::DRDY]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![1, 0, 255, 3, 255, 7]),
+    ]);
+    transactions
+}
+
+fn restore_transactions() -> [I2cTrans; 9] {
+    [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE | BF::DRDYE | BF::RES]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::DATA_CTRL, IDLE_ODR]),
+        I2cTrans::write(
+            DEV_ADDR,
+            vec![Reg::CTRL1, BF::PC1 | BF::WUFE | BF::DRDYE | BF::RES],
+        ),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::DATA_CTRL], vec![IDLE_ODR]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE | BF::DRDYE | BF::RES]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::WUFE | BF::DRDYE]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE | BF::DRDYE]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::WUFE]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_REL], vec![0]),
+    ]
+}
+
+#[test]
+fn captures_burst_on_motion() {
+    let mut transactions = burst_start_transactions();
+    transactions.extend_from_slice(&[
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![BF::DRDY]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![2, 0, 0, 0, 0, 0]),
+    ]);
+    transactions.extend_from_slice(&restore_transactions());
+    let mut sensor = new_1008(&transactions);
+    let mut capture = new_capture();
+    capture.arm(&mut sensor).unwrap();
+    let mut buffer = [UnscaledMeasurement::default(); 2];
+    let burst = capture
+        .capture(&mut sensor, &mut MockNoop::new(), &mut buffer)
+        .unwrap();
+    assert_eq!(Some((Axis::X, Direction::Negative)), burst.trigger);
+    assert_eq!(
+        InterruptInfo {
+            wake_up: true,
+            wake_up_x_negative: true,
+            ..Default::default()
+        },
+        burst.info
+    );
+    assert_eq!(
+        UnscaledMeasurement {
+            x: 1,
+            y: 1023,
+            z: 2047
+        },
+        buffer[0]
+    );
+    assert_eq!(UnscaledMeasurement { x: 2, y: 0, z: 0 }, buffer[1]);
+    destroy(sensor);
+}
+
+#[test]
+fn restores_idle_configuration_after_failed_read() {
+    let mut transactions = burst_start_transactions();
+    transactions.push(
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![BF::DRDY])
+            .with_error(MockError::Io(ErrorKind::Other)),
+    );
+    transactions.extend_from_slice(&restore_transactions());
+    let mut sensor = new_1008(&transactions);
+    let mut capture = new_capture();
+    capture.arm(&mut sensor).unwrap();
+    let mut buffer = [UnscaledMeasurement::default(); 2];
+    assert_eq!(
+        Err(nb::Error::Other(Error::I2C(MockError::Io(
+            ErrorKind::Other
+        )))),
+        capture.capture(&mut sensor, &mut MockNoop::new(), &mut buffer)
+    );
+    destroy(sensor);
+}
@@ -0,0 +1,61 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use hal::i2c::Transaction as I2cTrans;
+use kxcj9::{CaptureBuffer, CaptureConfig, CaptureTrigger, UnscaledMeasurement};
+
+mod common;
+use common::{destroy, new_1018, BitFlags as BF, Register as Reg, DEV_ADDR};
+
+fn wake_up_config() -> CaptureConfig {
+    CaptureConfig {
+        trigger: CaptureTrigger::WakeUp,
+        pre_trigger: 1,
+        post_trigger: 1,
+    }
+}
+
+#[test]
+fn can_sample_without_wake_up() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![1, 0, 2, 0, 3, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![0, 0]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    let mut storage = [UnscaledMeasurement::default(); 3];
+    let mut capture = CaptureBuffer::new(&mut storage, wake_up_config());
+    assert!(!capture.sample(&mut sensor).unwrap());
+    assert!(!capture.is_triggered());
+    destroy(sensor);
+}
+
+#[test]
+fn can_capture_on_wake_up() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![1, 0, 2, 0, 3, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![0, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![4, 0, 5, 0, 6, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![BF::WUFS, BF::XPWU]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_REL], vec![0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![7, 0, 8, 0, 9, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![0, 0]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    let mut storage = [UnscaledMeasurement::default(); 3];
+    let mut capture = CaptureBuffer::new(&mut storage, wake_up_config());
+    assert!(!capture.sample(&mut sensor).unwrap());
+    assert!(!capture.sample(&mut sensor).unwrap());
+    assert!(capture.sample(&mut sensor).unwrap());
+    // no further reads once the window is captured
+    assert!(capture.sample(&mut sensor).unwrap());
+    let window = capture.window().unwrap();
+    assert_eq!(1, window.trigger_index);
+    assert_eq!(
+        [1, 4, 7],
+        [
+            window.samples[0].x,
+            window.samples[1].x,
+            window.samples[2].x
+        ]
+    );
+    destroy(sensor);
+}
@@ -62,6 +62,7 @@ pub fn new_1041(transactions: &[I2cTrans]) -> Kxcj9<I2cMock, ic::G8Device> {
     Kxcj9::new_kxcjb_1041(I2cMock::new(transactions), SlaveAddr::default())
 }
 
+#[allow(unused)]
 pub fn destroy<IC>(sensor: Kxcj9<I2cMock, IC>) {
     sensor.destroy().done();
 }
@@ -0,0 +1,85 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use hal::delay::MockNoop;
+use hal::i2c::Transaction as I2cTrans;
+use kxcj9::{DutyCycleConfig, DutyCycleConfigError, DutyCycleSampler, Error};
+
+mod common;
+use common::{destroy, new_1018, BitFlags as BF, Register as Reg, DEV_ADDR};
+
+fn new_sampler(samples_per_wake: u16) -> DutyCycleSampler {
+    DutyCycleSampler::new(DutyCycleConfig::new(1000, samples_per_wake).unwrap())
+}
+
+fn assert_near(expected: f32, actual: f32) {
+    assert!(
+        (expected - actual).abs() < 1e-4,
+        "{} != {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn can_sample_and_average() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![32, 0, 0, 0, 64, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![0, 0, 32, 0, 64, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    let measurement = new_sampler(2)
+        .sample_and_wait(&mut sensor, &mut MockNoop::new())
+        .unwrap();
+    assert_near(0.5, measurement.x);
+    assert_near(0.5, measurement.y);
+    assert_near(2.0, measurement.z);
+    destroy(sensor);
+}
+
+#[test]
+fn disables_device_on_read_error() {
+    use hal::MockError;
+    use std::io::ErrorKind;
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![0; 6])
+            .with_error(MockError::Io(ErrorKind::Other)),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    assert_eq!(
+        Err(Error::I2C(MockError::Io(ErrorKind::Other))),
+        new_sampler(1).sample(&mut sensor, &mut MockNoop::new())
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn zero_samples_is_invalid() {
+    assert_eq!(
+        Err(DutyCycleConfigError::ZeroSampleCount),
+        DutyCycleConfig::new(1000, 0)
+    );
+}
+
+#[test]
+fn can_calculate_duty_cycle() {
+    let sensor = new_1018(&[]);
+    let sampler = new_sampler(3);
+    // 50 Hz, low resolution: 20.3ms start-up + 2 * 20ms
+    assert_eq!(60_300, sampler.active_time_us(&sensor));
+    assert_near(0.0603, sampler.duty_cycle(&sensor));
+    let expected = 0.0603 * kxcj9::LOW_POWER_CURRENT_UA + 0.9397 * kxcj9::STANDBY_CURRENT_UA;
+    assert_near(expected, sampler.average_current_ua(&sensor));
+    destroy(sensor);
+}
+
+#[test]
+fn duty_cycle_is_limited() {
+    let sensor = new_1018(&[]);
+    let sampler = DutyCycleSampler::new(DutyCycleConfig::new(10, 1).unwrap());
+    assert_near(1.0, sampler.duty_cycle(&sensor));
+    destroy(sensor);
+}
@@ -0,0 +1,78 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use hal::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use hal::MockError;
+use kxcj9::{Error, Resolution, SensorGroup, SlaveAddr, UnscaledMeasurement};
+use std::io::ErrorKind;
+
+mod common;
+use common::{BitFlags as BF, Register as Reg, DEV_ADDR};
+
+const ALT_ADDR: u8 = DEV_ADDR | 1;
+
+fn new_pair(transactions: &[I2cTrans]) -> SensorGroup<I2cMock, kxcj9::ic::G16Device, 2> {
+    SensorGroup::new_kxcj9_1018(
+        I2cMock::new(transactions),
+        [SlaveAddr::default(), SlaveAddr::Alternative(true)],
+    )
+}
+
+#[test]
+fn can_create_and_destroy() {
+    let group = new_pair(&[]);
+    assert_eq!(2, group.len());
+    assert!(!group.is_empty());
+    group.destroy().done();
+}
+
+#[test]
+fn can_enable_all() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+        I2cTrans::write(ALT_ADDR, vec![Reg::CTRL1, BF::PC1]),
+    ];
+    let mut group = new_pair(&transactions);
+    assert_eq!([Ok(()), Ok(())], group.enable());
+    group.destroy().done();
+}
+
+#[test]
+fn can_apply_same_configuration() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::RES]),
+        I2cTrans::write(ALT_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(ALT_ADDR, vec![Reg::CTRL1, BF::RES]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![1, 0, 0, 0, 0, 0]),
+        I2cTrans::write_read(ALT_ADDR, vec![Reg::XOUT_L], vec![2, 0, 0, 0, 0, 0]),
+    ];
+    let mut group = new_pair(&transactions);
+    let results = group.for_each(|sensor| sensor.set_resolution(Resolution::High));
+    assert_eq!([Ok(()), Ok(())], results);
+    let results = group.read_unscaled();
+    assert_eq!(Ok(UnscaledMeasurement { x: 1, y: 0, z: 0 }), results[0]);
+    assert_eq!(Ok(UnscaledMeasurement { x: 2, y: 0, z: 0 }), results[1]);
+    group.destroy().done();
+}
+
+#[test]
+fn reports_errors_per_sensor() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![0; 6])
+            .with_error(MockError::Io(ErrorKind::Other)),
+        I2cTrans::write_read(ALT_ADDR, vec![Reg::XOUT_L], vec![64, 0, 0, 0, 0, 0]),
+    ];
+    let mut group = new_pair(&transactions);
+    let results = group.read_unscaled();
+    assert_eq!(Err(Error::I2C(MockError::Io(ErrorKind::Other))), results[0]);
+    assert_eq!(Ok(UnscaledMeasurement { x: 64, y: 0, z: 0 }), results[1]);
+    group.destroy().done();
+}
+
+#[test]
+fn can_access_single_sensor() {
+    let transactions = [I2cTrans::write(ALT_ADDR, vec![Reg::CTRL1, BF::PC1])];
+    let mut group = new_pair(&transactions);
+    group.with_sensor(1, |sensor| sensor.enable()).unwrap();
+    group.destroy().done();
+}
@@ -1,7 +1,11 @@
 extern crate embedded_hal_mock as hal;
 extern crate kxcj9;
-use hal::i2c::Transaction as I2cTrans;
-use kxcj9::{GScale16, GScale8, OutputDataRate, Resolution};
+use hal::delay::MockNoop;
+use hal::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use kxcj9::{
+    Error, GScale16, GScale8, InterruptPinPolarity, Kxcj9, OutputDataRate, Resolution,
+    ScaledDevice, SelfTestStage, SettingError, WakeUpInterruptConfig, WakeUpOutputDataRate,
+};
 
 mod common;
 use common::{destroy, new_1008, new_1018, new_1041, BitFlags as BF, Register as Reg, DEV_ADDR};
@@ -60,9 +64,12 @@ macro_rules! cannot_set_res_low_for_odr {
             sensor
                 .set_output_data_rate(OutputDataRate::$variant)
                 .unwrap();
-            sensor
-                .set_resolution(Resolution::Low)
-                .expect_err("Should have returned error");
+            assert_eq!(
+                Err(Error::InvalidSetting(
+                    SettingError::LowResolutionAtHighDataRate
+                )),
+                sensor.set_resolution(Resolution::Low)
+            );
             destroy(sensor);
         }
     };
@@ -204,6 +211,77 @@ set_gscale_test!(
     BF::GSEL0 | BF::GSEL1 | BF::RES
 );
 
+macro_rules! set_range_at_least_test {
+    ($name:ident, $create:ident, $g:expr, $range:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let transactions = [
+                I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+                I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, $expected]),
+            ];
+            let mut sensor = $create(&transactions);
+            assert_eq!($range, sensor.set_range_at_least($g).unwrap());
+            assert_eq!($range, sensor.range_g());
+            destroy(sensor);
+        }
+    };
+}
+
+set_range_at_least_test!(range_at_least_1008_1g, new_1008, 1.0, 2.0, 0);
+set_range_at_least_test!(range_at_least_1008_2g, new_1008, 2.0, 2.0, 0);
+set_range_at_least_test!(range_at_least_1008_3g, new_1008, 3.0, 4.0, BF::GSEL0);
+set_range_at_least_test!(range_at_least_1008_8g, new_1008, 8.0, 8.0, BF::GSEL1);
+set_range_at_least_test!(range_at_least_1018_1g, new_1018, 1.0, 4.0, 0);
+set_range_at_least_test!(range_at_least_1018_9g, new_1018, 9.0, 16.0, BF::GSEL1);
+
+#[test]
+fn cannot_set_range_larger_than_device_range() {
+    let mut sensor = new_1008(&[]);
+    assert_eq!(
+        Err(Error::InvalidSetting(SettingError::RangeOutOfRange)),
+        sensor.set_range_at_least(8.5)
+    );
+    destroy(sensor);
+    let mut sensor = new_1018(&[]);
+    assert_eq!(
+        Err(Error::InvalidSetting(SettingError::RangeOutOfRange)),
+        sensor.set_range_at_least(16.5)
+    );
+    destroy(sensor);
+}
+
+fn set_largest_scale<IC: ScaledDevice>(sensor: &mut Kxcj9<I2cMock, IC>) -> f32 {
+    let max_range = sensor.capabilities().max_range_g();
+    sensor.set_range_at_least(max_range).unwrap()
+}
+
+#[test]
+fn can_configure_range_in_generic_code() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::GSEL1]),
+    ];
+    let mut sensor = new_1008(&transactions);
+    assert_eq!(8.0, set_largest_scale(&mut sensor));
+    destroy(sensor);
+    let mut sensor = new_1018(&transactions);
+    assert_eq!(16.0, set_largest_scale(&mut sensor));
+    destroy(sensor);
+}
+
+#[test]
+fn full_power_scale_reports_largest_range() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::GSEL0 | BF::GSEL1 | BF::RES]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    assert_eq!(4.0, sensor.range_g());
+    sensor.set_scale(GScale16::G16FP).unwrap();
+    assert_eq!(16.0, sensor.range_g());
+    destroy(sensor);
+}
+
 #[test]
 fn can_trigger_sw_reset_then_driver_configuration_is_reset() {
     let transactions = [
@@ -224,6 +302,60 @@ fn can_trigger_sw_reset_then_driver_configuration_is_reset() {
     destroy(sensor);
 }
 
+#[test]
+fn can_reset_blocking() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::RES]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, BF::SRST]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::CTRL2], vec![BF::SRST]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::CTRL2], vec![0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    sensor.set_resolution(Resolution::High).unwrap();
+    sensor.reset_blocking(&mut MockNoop::new(), 50).unwrap();
+    sensor.enable().unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn reset_blocking_can_time_out() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, BF::SRST]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::CTRL2], vec![BF::SRST]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::CTRL2], vec![BF::SRST]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::CTRL2], vec![BF::SRST]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    assert_eq!(
+        Err(Error::ResetTimeout),
+        sensor.reset_blocking(&mut MockNoop::new(), 12)
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn reset_restores_interrupt_pin_configuration() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, BF::SRST]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::CTRL2], vec![0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL1, BF::IEA | BF::IEN]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    sensor
+        .set_interrupt_pin_polarity(InterruptPinPolarity::ActiveLow)
+        .unwrap();
+    sensor.reset_blocking(&mut MockNoop::new(), 50).unwrap();
+    sensor.enable_interrupt_pin().unwrap();
+    destroy(sensor);
+}
+
 #[test]
 fn can_perform_communication_self_test() {
     let transactions = [
@@ -246,9 +378,10 @@ fn communication_self_test_can_fail_in_step1() {
         vec![0x56],
     )];
     let mut sensor = new_1018(&transactions);
-    sensor
-        .communication_self_test()
-        .expect_err("Should return error");
+    assert_eq!(
+        Err(Error::SelfTestError(SelfTestStage::InitialResponse)),
+        sensor.communication_self_test()
+    );
     destroy(sensor);
 }
 
@@ -260,9 +393,10 @@ fn communication_self_test_can_fail_in_step2() {
         I2cTrans::write_read(DEV_ADDR, vec![Reg::DCST_RESP], vec![0xAB]),
     ];
     let mut sensor = new_1018(&transactions);
-    sensor
-        .communication_self_test()
-        .expect_err("Should return error");
+    assert_eq!(
+        Err(Error::SelfTestError(SelfTestStage::TestResponse)),
+        sensor.communication_self_test()
+    );
     destroy(sensor);
 }
 
@@ -275,9 +409,10 @@ fn communication_self_test_can_fail_in_step3() {
         I2cTrans::write_read(DEV_ADDR, vec![Reg::CTRL2], vec![BF::DCST]),
     ];
     let mut sensor = new_1018(&transactions);
-    sensor
-        .communication_self_test()
-        .expect_err("Should return error");
+    assert_eq!(
+        Err(Error::SelfTestError(SelfTestStage::DcstNotCleared)),
+        sensor.communication_self_test()
+    );
     destroy(sensor);
 }
 
@@ -291,9 +426,10 @@ fn communication_self_test_can_fail_in_step4() {
         I2cTrans::write_read(DEV_ADDR, vec![Reg::DCST_RESP], vec![0x56]),
     ];
     let mut sensor = new_1018(&transactions);
-    sensor
-        .communication_self_test()
-        .expect_err("Should return error");
+    assert_eq!(
+        Err(Error::SelfTestError(SelfTestStage::FinalResponse)),
+        sensor.communication_self_test()
+    );
     destroy(sensor);
 }
 
@@ -312,3 +448,148 @@ fn can_disable_mems_self_test() {
     sensor.disable_mems_self_test().unwrap();
     destroy(sensor);
 }
+
+macro_rules! who_am_i_test {
+    ($name:ident, $create:ident, $who_am_i:expr) => {
+        #[test]
+        fn $name() {
+            let transactions = [I2cTrans::write_read(
+                DEV_ADDR,
+                vec![Reg::WHO_AM_I],
+                vec![$who_am_i],
+            )];
+            let mut sensor = $create(&transactions);
+            sensor.verify_who_am_i().unwrap();
+            destroy(sensor);
+        }
+    };
+}
+who_am_i_test!(can_verify_who_am_i_1008, new_1008, 0x0A);
+who_am_i_test!(can_verify_who_am_i_1018, new_1018, 0x1D);
+who_am_i_test!(can_verify_who_am_i_1041, new_1041, 0x21);
+
+#[test]
+fn verify_who_am_i_reports_wrong_device() {
+    let transactions = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Reg::WHO_AM_I],
+        vec![0x0A],
+    )];
+    let mut sensor = new_1018(&transactions);
+    assert_eq!(
+        Err(Error::WrongDevice { who_am_i: 0x0A }),
+        sensor.verify_who_am_i()
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn errors_can_be_displayed() {
+    assert_eq!(
+        "self-test failed: DCST bit was not cleared",
+        format!(
+            "{}",
+            Error::SelfTestError::<()>(SelfTestStage::DcstNotCleared)
+        )
+    );
+    assert_eq!(
+        "unexpected WHO_AM_I value: 0x0a",
+        format!("{}", Error::WrongDevice::<()> { who_am_i: 0x0A })
+    );
+    assert_eq!("I2C bus error: 5", format!("{}", Error::I2C(5)));
+}
+
+#[test]
+fn can_enable_and_wait() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::DATA_CTRL, 4]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    sensor.set_output_data_rate(OutputDataRate::Hz200).unwrap();
+    assert_eq!(5_300, sensor.start_up_time_us());
+    sensor.enable_and_wait(&mut MockNoop::new()).unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn start_up_time_depends_on_resolution() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::RES]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    assert_eq!(20_300, sensor.start_up_time_us());
+    sensor.set_resolution(Resolution::High).unwrap();
+    assert_eq!(21_200, sensor.start_up_time_us());
+    destroy(sensor);
+}
+
+#[test]
+fn start_up_time_at_high_odr_does_not_depend_on_resolution() {
+    assert_eq!(
+        OutputDataRate::Hz800.start_up_time_us(Resolution::Low),
+        OutputDataRate::Hz800.start_up_time_us(Resolution::High)
+    );
+}
+
+#[test]
+fn typical_current_follows_configuration() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::DATA_CTRL, 5]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::RES]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    assert_eq!(kxcj9::STANDBY_CURRENT_UA, sensor.typical_current_ua());
+    sensor.enable().unwrap();
+    assert_eq!(kxcj9::LOW_POWER_CURRENT_UA, sensor.typical_current_ua());
+    sensor.set_output_data_rate(OutputDataRate::Hz400).unwrap();
+    assert_eq!(
+        kxcj9::HIGH_RESOLUTION_CURRENT_UA,
+        sensor.typical_current_ua()
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn typical_current_includes_wake_up_function() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL2, 0b0011_1111]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_TIMER, 1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_THRESHOLD, 8]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::WUFE]),
+    ];
+    let mut sensor = new_1008(&transactions);
+    sensor
+        .enable_wake_up_interrupt(WakeUpInterruptConfig::default())
+        .unwrap();
+    sensor.enable().unwrap();
+    let config = sensor.power_config();
+    assert_eq!(
+        Some(WakeUpOutputDataRate::Hz0_781),
+        config.wake_up_data_rate
+    );
+    let expected = kxcj9::LOW_POWER_CURRENT_UA + 1.04 - kxcj9::STANDBY_CURRENT_UA;
+    assert!((expected - sensor.typical_current_ua()).abs() < 1e-4);
+    destroy(sensor);
+}
+
+#[test]
+fn capabilities_match_device_kind() {
+    let sensor = new_1008(&[]);
+    let caps = sensor.capabilities();
+    assert_eq!([2.0, 4.0, 8.0], caps.ranges_g);
+    assert_eq!(kxcj9::Capabilities::KXCJ9_1008, caps);
+    assert_eq!(12, caps.data_rates.len());
+    destroy(sensor);
+
+    let sensor = new_1018(&[]);
+    assert_eq!(kxcj9::Capabilities::KXCJ9_1018, sensor.capabilities());
+    destroy(sensor);
+}
@@ -0,0 +1,150 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+extern crate nb;
+use hal::i2c::Transaction as I2cTrans;
+use hal::pin::{Mock as PinMock, State as PinState, Transaction as PinTrans};
+use kxcj9::{InterruptInfo, InterruptPinPolarity};
+
+mod common;
+use common::{destroy, new_1018, BitFlags as BF, Register as Reg, DEV_ADDR};
+
+#[test]
+fn interrupt_pin_is_inactive() {
+    let mut sensor =
+        new_1018(&[]).with_interrupt_pin(PinMock::new(&[PinTrans::get(PinState::Low)]));
+    assert_eq!(Err(nb::Error::WouldBlock), sensor.wait_for_interrupt());
+    let (sensor, mut pin) = sensor.destroy();
+    pin.done();
+    destroy(sensor);
+}
+
+#[test]
+fn can_wait_for_interrupt() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![BF::WUFS, BF::ZPWU]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_REL], vec![0]),
+    ];
+    let pin = PinMock::new(&[PinTrans::get(PinState::High)]);
+    let mut sensor = new_1018(&transactions).with_interrupt_pin(pin);
+    assert_eq!(
+        InterruptInfo {
+            wake_up: true,
+            wake_up_z_positive: true,
+            ..Default::default()
+        },
+        sensor.wait_for_interrupt().unwrap()
+    );
+    let (sensor, mut pin) = sensor.destroy();
+    pin.done();
+    destroy(sensor);
+}
+
+#[test]
+fn interrupt_pin_level_follows_polarity() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+    ];
+    let pin = PinMock::new(&[PinTrans::get(PinState::Low)]);
+    let mut sensor = new_1018(&transactions).with_interrupt_pin(pin);
+    sensor
+        .sensor()
+        .set_interrupt_pin_polarity(InterruptPinPolarity::ActiveLow)
+        .unwrap();
+    assert!(sensor.is_interrupt_active().unwrap());
+    let (sensor, mut pin) = sensor.destroy();
+    pin.done();
+    destroy(sensor);
+}
+
+#[cfg(feature = "async")]
+mod asynchronous {
+    extern crate embedded_hal_1;
+    extern crate embedded_hal_async;
+    use super::*;
+    use core::convert::Infallible;
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    #[derive(Default)]
+    struct FakePin {
+        waited_for: Vec<&'static str>,
+    }
+
+    impl embedded_hal_1::digital::ErrorType for FakePin {
+        type Error = Infallible;
+    }
+
+    impl embedded_hal_async::digital::Wait for FakePin {
+        async fn wait_for_high(&mut self) -> Result<(), Infallible> {
+            self.waited_for.push("high");
+            Ok(())
+        }
+        async fn wait_for_low(&mut self) -> Result<(), Infallible> {
+            self.waited_for.push("low");
+            Ok(())
+        }
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Infallible> {
+            self.waited_for.push("rising");
+            Ok(())
+        }
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Infallible> {
+            self.waited_for.push("falling");
+            Ok(())
+        }
+        async fn wait_for_any_edge(&mut self) -> Result<(), Infallible> {
+            self.waited_for.push("any");
+            Ok(())
+        }
+    }
+
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        // Equivalent to `Waker::noop()`, which requires Rust 1.85.
+        let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+        let mut context = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn can_wait_for_interrupt_async() {
+        let transactions = [
+            I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+            I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL1, 0]),
+            I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+            I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+            I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL1, BF::IEL]),
+            I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+            I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![BF::DRDY, 0]),
+            I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_REL], vec![0]),
+        ];
+        let mut sensor = new_1018(&transactions);
+        sensor
+            .set_interrupt_pin_polarity(InterruptPinPolarity::ActiveLow)
+            .unwrap();
+        sensor
+            .set_interrupt_pin_latching(kxcj9::InterruptPinLatching::NonLatching)
+            .unwrap();
+        let mut sensor = sensor.with_interrupt_pin(FakePin::default());
+        let info = block_on(sensor.wait_for_interrupt_async()).unwrap();
+        assert!(info.data_ready);
+        let (sensor, pin) = sensor.destroy();
+        assert_eq!(vec!["falling"], pin.waited_for);
+        destroy(sensor);
+    }
+}
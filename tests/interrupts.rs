@@ -2,8 +2,9 @@ extern crate embedded_hal_mock as hal;
 extern crate kxcj9;
 use hal::i2c::Transaction as I2cTrans;
 use kxcj9::{
-    InterruptInfo, InterruptPinLatching as IPL, InterruptPinPolarity as IPPOL,
-    WakeUpInterruptConfig, WakeUpOutputDataRate, WakeUpTriggerMotion,
+    Axis, Direction, Error, Event, InterruptInfo, InterruptPinLatching as IPL,
+    InterruptPinPolarity as IPPOL, SettingError, WakeUpInterruptConfig, WakeUpOutputDataRate,
+    WakeUpTriggerMotion,
 };
 
 mod common;
@@ -274,9 +275,10 @@ fn cannot_set_wake_up_fault_count_0() {
         fault_count: 0,
         ..Default::default()
     };
-    sensor
-        .enable_wake_up_interrupt(config)
-        .expect_err("Should return error");
+    assert_eq!(
+        Err(Error::InvalidSetting(SettingError::ZeroFaultCount)),
+        sensor.enable_wake_up_interrupt(config)
+    );
     destroy(sensor);
 }
 
@@ -289,9 +291,10 @@ macro_rules! wrong_th_test {
                 threshold: $threshold,
                 ..Default::default()
             };
-            sensor
-                .enable_wake_up_interrupt(config)
-                .expect_err("Should return error");
+            assert_eq!(
+                Err(Error::InvalidSetting(SettingError::ThresholdOutOfRange)),
+                sensor.enable_wake_up_interrupt(config)
+            );
             destroy(sensor);
         }
     };
@@ -367,3 +370,42 @@ int_pin_test!(
     BF::IEA | BF::IEL,
     IPL::NonLatching
 );
+
+#[test]
+fn can_poll_events() {
+    let transactions = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Reg::INT_SOURCE1],
+        vec![BF::DRDY | BF::WUFS, BF::YNWU],
+    )];
+    let mut sensor = new_1018(&transactions);
+    let mut events = sensor.poll_events().unwrap();
+    assert_eq!(Some(Event::DataReady), events.next());
+    assert_eq!(
+        Some(Event::Motion {
+            axis: Axis::Y,
+            direction: Direction::Negative
+        }),
+        events.next()
+    );
+    assert_eq!(None, events.next());
+    destroy(sensor);
+}
+
+#[test]
+fn can_poll_events_and_clear() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![BF::WUFS, BF::XPWU]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_REL], vec![0]),
+    ];
+    let mut sensor = new_1018(&transactions);
+    let events: Vec<Event> = sensor.poll_events_and_clear().unwrap().collect();
+    assert_eq!(
+        vec![Event::Motion {
+            axis: Axis::X,
+            direction: Direction::Positive
+        }],
+        events
+    );
+    destroy(sensor);
+}
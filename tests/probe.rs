@@ -0,0 +1,91 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use hal::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use hal::MockError;
+use kxcj9::{probe, DeviceVariant, ProbeResult, SlaveAddr};
+use std::io::ErrorKind;
+
+mod common;
+use common::{Register as Reg, DEV_ADDR};
+
+const ALT_ADDR: u8 = DEV_ADDR | 1;
+
+fn nack(address: u8, register: u8) -> I2cTrans {
+    I2cTrans::write_read(address, vec![register], vec![0])
+        .with_error(MockError::Io(ErrorKind::Other))
+}
+
+#[test]
+fn can_find_devices_at_both_addresses() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::WHO_AM_I], vec![0x1D]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::DCST_RESP], vec![0x55]),
+        I2cTrans::write_read(ALT_ADDR, vec![Reg::WHO_AM_I], vec![0x21]),
+        I2cTrans::write_read(ALT_ADDR, vec![Reg::DCST_RESP], vec![0x55]),
+    ];
+    let mut i2c = I2cMock::new(&transactions);
+    let results = probe(&mut i2c);
+    assert_eq!(
+        ProbeResult {
+            address: SlaveAddr::Default,
+            who_am_i: Some(0x1D),
+            dcst_resp: Some(0x55),
+            error: None,
+        },
+        results[0]
+    );
+    assert_eq!(Some(DeviceVariant::Kxcj9_1018), results[0].variant());
+    assert_eq!(SlaveAddr::Alternative(true), results[1].address);
+    assert_eq!(Some(DeviceVariant::Kxcjb_1041), results[1].variant());
+    i2c.done();
+}
+
+#[test]
+fn missing_device_is_not_present() {
+    let transactions = [
+        nack(DEV_ADDR, Reg::WHO_AM_I),
+        I2cTrans::write_read(ALT_ADDR, vec![Reg::WHO_AM_I], vec![0x0A]),
+        I2cTrans::write_read(ALT_ADDR, vec![Reg::DCST_RESP], vec![0x55]),
+    ];
+    let mut i2c = I2cMock::new(&transactions);
+    let results = probe(&mut i2c);
+    assert!(!results[0].is_present());
+    assert_eq!(Some(MockError::Io(ErrorKind::Other)), results[0].error);
+    assert_eq!(None, results[0].variant());
+    assert!(results[1].is_present());
+    assert_eq!(Some(DeviceVariant::Kxcj9_1008), results[1].variant());
+    i2c.done();
+}
+
+#[test]
+fn unknown_device_has_no_variant() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::WHO_AM_I], vec![0xAB]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::DCST_RESP], vec![0x55]),
+        I2cTrans::write_read(ALT_ADDR, vec![Reg::WHO_AM_I], vec![0x0A]),
+        I2cTrans::write_read(ALT_ADDR, vec![Reg::DCST_RESP], vec![0x12]),
+    ];
+    let mut i2c = I2cMock::new(&transactions);
+    let results = probe(&mut i2c);
+    assert!(results[0].is_present());
+    assert_eq!(None, results[0].variant());
+    assert!(results[1].is_present());
+    assert_eq!(None, results[1].variant());
+    i2c.done();
+}
+
+#[test]
+fn failed_dcst_resp_read_has_no_variant() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::WHO_AM_I], vec![0x0A]),
+        nack(DEV_ADDR, Reg::DCST_RESP),
+        nack(ALT_ADDR, Reg::WHO_AM_I),
+    ];
+    let mut i2c = I2cMock::new(&transactions);
+    let results = probe(&mut i2c);
+    assert_eq!(Some(0x0A), results[0].who_am_i);
+    assert_eq!(None, results[0].dcst_resp);
+    assert_eq!(Some(MockError::Io(ErrorKind::Other)), results[0].error);
+    assert_eq!(None, results[0].variant());
+    i2c.done();
+}
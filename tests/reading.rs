@@ -1,7 +1,9 @@
 extern crate embedded_hal_mock as hal;
 extern crate kxcj9;
+extern crate nb;
+use hal::delay::MockNoop;
 use hal::i2c::Transaction as I2cTrans;
-use kxcj9::{GScale16, Resolution};
+use kxcj9::{Error, GScale16, Resolution};
 
 mod common;
 use common::{destroy, new_1018, BitFlags, Register, DEV_ADDR};
@@ -151,3 +153,136 @@ fn can_read_14bit_16g_1018() {
     assert_near_positive(16.0, measurement.z);
     destroy(sensor);
 }
+
+#[test]
+fn can_read_m_s2() {
+    let transactions = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Register::XOUT_L],
+        vec![0, 0xAB, 32, 0xCD, 224, 0xEF],
+    )];
+    let mut sensor = new_1018(&transactions);
+    let measurement = sensor.read_m_s2().unwrap();
+    assert_near_positive(0.0, measurement.x);
+    assert_near_positive(9.80665, measurement.y);
+    assert_near_positive(-9.80665, measurement.z);
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_m_s2_with_custom_standard_gravity() {
+    let transactions = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Register::XOUT_L],
+        vec![0, 0xAB, 32, 0xCD, 64, 0xEF],
+    )];
+    let mut sensor = new_1018(&transactions);
+    sensor.set_standard_gravity(10.0);
+    let measurement = sensor.read_m_s2().unwrap();
+    assert_near_positive(0.0, measurement.x);
+    assert_near_positive(10.0, measurement.y);
+    assert_near_positive(20.0, measurement.z);
+    destroy(sensor);
+}
+
+#[cfg(feature = "uom")]
+mod typed_units {
+    extern crate uom;
+    use self::uom::si::acceleration::{meter_per_second_squared, standard_gravity};
+    use self::uom::si::f32::Acceleration;
+    use super::*;
+    use kxcj9::WakeUpInterruptConfig;
+
+    #[test]
+    fn can_read_acceleration() {
+        let transactions = [I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Register::XOUT_L],
+            vec![0, 0xAB, 32, 0xCD, 224, 0xEF],
+        )];
+        let mut sensor = new_1018(&transactions);
+        let measurement = sensor.read_acceleration().unwrap();
+        assert_near_positive(0.0, measurement.x.get::<meter_per_second_squared>());
+        assert_near_positive(9.80665, measurement.y.get::<meter_per_second_squared>());
+        assert_near_positive(-1.0, measurement.z.get::<standard_gravity>());
+        destroy(sensor);
+    }
+
+    #[test]
+    fn can_convert_wake_up_threshold() {
+        let mut config = WakeUpInterruptConfig::default();
+        config.set_threshold_acceleration(Acceleration::new::<meter_per_second_squared>(19.6133));
+        assert_near_positive(2.0, config.threshold);
+        assert_near_positive(
+            2.0,
+            config.threshold_acceleration().get::<standard_gravity>(),
+        );
+    }
+}
+
+#[test]
+fn read_new_blocks_without_new_data() {
+    let transactions = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Register::INT_SOURCE1],
+        vec![0],
+    )];
+    let mut sensor = new_1018(&transactions);
+    assert_eq!(Err(nb::Error::WouldBlock), sensor.read_new());
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_new() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Register::INT_SOURCE1], vec![BitFlags::DRDY]),
+        I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Register::XOUT_L],
+            vec![0, 0xAB, 64, 0xCD, 127, 0xEF],
+        ),
+    ];
+    let mut sensor = new_1018(&transactions);
+    let measurement = sensor.read_new().unwrap();
+    assert_near_positive(0.0, measurement.x);
+    assert_near_positive(2.0, measurement.y);
+    assert_near_positive(4.0, measurement.z);
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_unscaled_new_blocking() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Register::INT_SOURCE1], vec![0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::INT_SOURCE1], vec![0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::INT_SOURCE1], vec![BitFlags::DRDY]),
+        I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Register::XOUT_L],
+            vec![0, 0xAB, 127, 0xCD, 128, 0xEF],
+        ),
+    ];
+    let mut sensor = new_1018(&transactions);
+    let measurement = sensor
+        .read_unscaled_new_blocking(&mut MockNoop::new())
+        .unwrap();
+    assert_eq!(0, measurement.x);
+    assert_eq!(127, measurement.y);
+    assert_eq!(-128, measurement.z);
+    destroy(sensor);
+}
+
+#[test]
+fn read_new_blocking_times_out() {
+    // 50 Hz in low resolution: 20.3 ms start-up time + 20 ms period,
+    // polled every 5 ms.
+    let transactions: Vec<_> = (0..10)
+        .map(|_| I2cTrans::write_read(DEV_ADDR, vec![Register::INT_SOURCE1], vec![0]))
+        .collect();
+    let mut sensor = new_1018(&transactions);
+    assert_eq!(
+        Err(Error::ReadTimeout),
+        sensor.read_unscaled_new_blocking(&mut MockNoop::new())
+    );
+    destroy(sensor);
+}
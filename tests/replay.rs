@@ -0,0 +1,125 @@
+extern crate embedded_hal as ehal;
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use ehal::blocking::i2c::{Write, WriteRead};
+use hal::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use hal::MockError;
+use kxcj9::{
+    Error, Kxcj9, ReplayError, ReplayI2c, SlaveAddr, TextRecorder, TracingI2c, UnscaledMeasurement,
+};
+use std::io::ErrorKind;
+
+mod common;
+use common::{new_1008, BitFlags as BF, Register as Reg, DEV_ADDR};
+
+#[test]
+fn can_record_session() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], vec![1, 0, 2, 0, 3, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::WHO_AM_I], vec![0])
+            .with_error(MockError::Io(ErrorKind::Other)),
+    ];
+    let mut sensor = new_1008(&transactions).with_trace_hook(TextRecorder::new(String::new()));
+    sensor.enable().unwrap();
+    sensor.read_unscaled().unwrap();
+    assert!(sensor.who_am_i().is_err());
+    let (mut i2c, recorder) = sensor.destroy().destroy();
+    i2c.done();
+    assert!(!recorder.has_error());
+    assert_eq!(
+        "w 0e 1b 80\nr 0e 06 01 00 02 00 03 00\n!r 0e 0f 00\n",
+        recorder.destroy()
+    );
+}
+
+const SESSION: &str = "
+# enable and read
+w 0e 1b 80
+r 0e 06 01 00 02 00 03 00
+";
+
+#[test]
+fn can_replay_session() {
+    let mut sensor = Kxcj9::new_kxcj9_1008(ReplayI2c::new(SESSION), SlaveAddr::default());
+    sensor.enable().unwrap();
+    assert!(!sensor.i2c().is_finished());
+    assert_eq!(
+        UnscaledMeasurement { x: 1, y: 2, z: 3 },
+        sensor.read_unscaled().unwrap()
+    );
+    assert!(sensor.i2c().is_finished());
+    assert_eq!(0, sensor.i2c().divergences());
+    assert_eq!(
+        Err(Error::I2C(ReplayError::EndOfRecording)),
+        sensor.read_unscaled()
+    );
+}
+
+#[test]
+fn flags_divergent_writes() {
+    let mut sensor = Kxcj9::new_kxcj9_1008(ReplayI2c::new(SESSION), SlaveAddr::default());
+    sensor.disable().unwrap();
+    assert_eq!(1, sensor.i2c().divergences());
+    assert_eq!(Some(3), sensor.i2c().first_divergence());
+    sensor.read_unscaled().unwrap();
+}
+
+#[test]
+fn reports_unexpected_transactions() {
+    let mut sensor = Kxcj9::new_kxcj9_1008(ReplayI2c::new(SESSION), SlaveAddr::default());
+    assert_eq!(
+        Err(Error::I2C(ReplayError::UnexpectedTransaction { line: 3 })),
+        sensor.who_am_i()
+    );
+}
+
+#[test]
+fn replays_recorded_errors() {
+    let mut sensor = Kxcj9::new_kxcj9_1008(ReplayI2c::new("!r 0e 0f 00"), SlaveAddr::default());
+    assert_eq!(
+        Err(Error::I2C(ReplayError::RecordedError { line: 1 })),
+        sensor.who_am_i()
+    );
+}
+
+#[test]
+fn reports_invalid_recordings() {
+    let mut sensor = Kxcj9::new_kxcj9_1008(ReplayI2c::new("\nw 0e"), SlaveAddr::default());
+    assert_eq!(
+        Err(Error::I2C(ReplayError::InvalidRecording { line: 2 })),
+        sensor.enable()
+    );
+}
+
+#[test]
+fn can_round_trip_long_transfers() {
+    let data: Vec<u8> = (1..=10).collect();
+    let mut write = vec![Reg::XOUT_L];
+    write.extend_from_slice(&data);
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, write.clone()),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::XOUT_L], data.clone()),
+    ];
+    let mut i2c = TracingI2c::new(
+        I2cMock::new(&transactions),
+        TextRecorder::new(String::new()),
+    );
+    i2c.write(DEV_ADDR, &write).unwrap();
+    let mut buffer = [0; 10];
+    i2c.write_read(DEV_ADDR, &[Reg::XOUT_L], &mut buffer)
+        .unwrap();
+    let (mut mock, recorder) = i2c.destroy();
+    mock.done();
+    let recording = recorder.destroy();
+
+    let mut replay = ReplayI2c::new(&recording);
+    replay.write(DEV_ADDR, &write).unwrap();
+    let mut replayed = [0; 10];
+    replay
+        .write_read(DEV_ADDR, &[Reg::XOUT_L], &mut replayed)
+        .unwrap();
+    assert_eq!(0, replay.divergences());
+    assert_eq!(&data[..], &replayed[..]);
+    assert!(replay.is_finished());
+}
@@ -0,0 +1,160 @@
+extern crate embedded_hal as ehal;
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use ehal::blocking::delay::DelayUs;
+use hal::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use hal::MockError;
+use kxcj9::{Error, Kxcj9, RetryPolicy, RetryStats, RetryingI2c, SlaveAddr};
+use std::io::ErrorKind;
+
+mod common;
+use common::{BitFlags as BF, Register as Reg, DEV_ADDR};
+
+#[derive(Debug, Default)]
+struct RecordingDelay {
+    delays_us: Vec<u32>,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.delays_us.push(us);
+    }
+}
+
+type Sensor = Kxcj9<RetryingI2c<I2cMock, RecordingDelay>, kxcj9::ic::G8Device>;
+
+fn new(transactions: &[I2cTrans], policy: RetryPolicy) -> Sensor {
+    let i2c = RetryingI2c::new(
+        I2cMock::new(transactions),
+        RecordingDelay::default(),
+        policy,
+    );
+    Kxcj9::new_kxcj9_1008(i2c, SlaveAddr::default())
+}
+
+fn destroy(sensor: Sensor) -> RecordingDelay {
+    let (mut i2c, delay) = sensor.destroy().destroy();
+    i2c.done();
+    delay
+}
+
+fn failed_write(bytes: Vec<u8>) -> I2cTrans {
+    I2cTrans::write(DEV_ADDR, bytes).with_error(MockError::Io(ErrorKind::Other))
+}
+
+fn failed_write_read(register: u8) -> I2cTrans {
+    I2cTrans::write_read(DEV_ADDR, vec![register], vec![0])
+        .with_error(MockError::Io(ErrorKind::Other))
+}
+
+#[test]
+fn passes_successful_transactions_through() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::WHO_AM_I], vec![0x0A]),
+    ];
+    let mut sensor = new(&transactions, RetryPolicy::default());
+    sensor.enable().unwrap();
+    assert_eq!(0x0A, sensor.who_am_i().unwrap());
+    assert_eq!(RetryStats::default(), sensor.i2c().stats());
+    assert!(destroy(sensor).delays_us.is_empty());
+}
+
+#[test]
+fn retries_failed_write_with_backoff() {
+    let transactions = [
+        failed_write(vec![Reg::CTRL1, BF::PC1]),
+        failed_write(vec![Reg::CTRL1, BF::PC1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+    ];
+    let mut sensor = new(&transactions, RetryPolicy::default());
+    sensor.enable().unwrap();
+    let stats = sensor.i2c().stats();
+    assert_eq!(2, stats.retries);
+    assert_eq!(0, stats.failures);
+    assert_eq!(vec![100, 200], destroy(sensor).delays_us);
+}
+
+#[test]
+fn retries_failed_read() {
+    let transactions = [
+        failed_write_read(Reg::WHO_AM_I),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::WHO_AM_I], vec![0x0A]),
+    ];
+    let mut sensor = new(&transactions, RetryPolicy::default());
+    assert_eq!(0x0A, sensor.who_am_i().unwrap());
+    assert_eq!(1, sensor.i2c().stats().retries);
+    destroy(sensor);
+}
+
+#[test]
+fn returns_error_after_max_attempts() {
+    let transactions = [
+        failed_write_read(Reg::WHO_AM_I),
+        failed_write_read(Reg::WHO_AM_I),
+        failed_write_read(Reg::WHO_AM_I),
+    ];
+    let mut sensor = new(&transactions, RetryPolicy::default());
+    match sensor.who_am_i() {
+        Err(Error::I2C(MockError::Io(ErrorKind::Other))) => (),
+        _ => panic!("Error not returned."),
+    }
+    assert_eq!(
+        RetryStats {
+            retries: 2,
+            failures: 1
+        },
+        sensor.i2c().stats()
+    );
+    sensor.i2c_mut().reset_stats();
+    assert_eq!(RetryStats::default(), sensor.i2c().stats());
+    destroy(sensor);
+}
+
+#[test]
+fn does_not_retry_disabled_operations() {
+    let transactions = [
+        failed_write(vec![Reg::CTRL1, BF::PC1]),
+        failed_write_read(Reg::WHO_AM_I),
+        failed_write_read(Reg::WHO_AM_I),
+    ];
+    let policy = RetryPolicy {
+        retry_writes: false,
+        max_attempts: 2,
+        ..RetryPolicy::default()
+    };
+    let mut sensor = new(&transactions, policy);
+    assert!(sensor.enable().is_err());
+    assert!(sensor.who_am_i().is_err());
+    assert_eq!(
+        RetryStats {
+            retries: 1,
+            failures: 2
+        },
+        sensor.i2c().stats()
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn does_not_retry_software_reset() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::CTRL2], vec![0]),
+        failed_write(vec![Reg::CTRL2, BF::SRST]),
+    ];
+    let mut sensor = new(&transactions, RetryPolicy::default());
+    assert!(sensor.reset().is_err());
+    assert_eq!(1, sensor.i2c().stats().failures);
+    assert_eq!(0, sensor.i2c().stats().retries);
+    destroy(sensor);
+}
+
+#[test]
+fn does_not_retry_interrupt_release() {
+    let transactions = [failed_write_read(Reg::INT_REL)];
+    let mut sensor = new(&transactions, RetryPolicy::default());
+    assert!(sensor.clear_interrupts().is_err());
+    assert_eq!(1, sensor.i2c().stats().failures);
+    assert_eq!(0, sensor.i2c().stats().retries);
+    destroy(sensor);
+}
@@ -0,0 +1,88 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use hal::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use kxcj9::{bus::RefCellDevice, Kxcj9, SlaveAddr};
+use std::cell::RefCell;
+
+mod common;
+use common::{destroy, BitFlags as BF, Register as Reg, DEV_ADDR};
+
+const ALT_ADDR: u8 = DEV_ADDR | 1;
+
+fn shared_transactions() -> [I2cTrans; 2] {
+    [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+        I2cTrans::write(ALT_ADDR, vec![Reg::CTRL1, BF::PC1]),
+    ]
+}
+
+#[test]
+fn can_share_bus_with_refcell() {
+    let bus = RefCell::new(I2cMock::new(&shared_transactions()));
+    let mut sensor1 = Kxcj9::new_kxcj9_1018(RefCellDevice::new(&bus), SlaveAddr::default());
+    let mut sensor2 = Kxcj9::new_kxcj9_1018(RefCellDevice::new(&bus), SlaveAddr::Alternative(true));
+    sensor1.enable().unwrap();
+    sensor2.enable().unwrap();
+    bus.into_inner().done();
+}
+
+#[cfg(feature = "critical-section")]
+#[test]
+fn can_share_bus_with_critical_section() {
+    use kxcj9::bus::CriticalSectionDevice;
+    let bus = critical_section::Mutex::new(RefCell::new(I2cMock::new(&shared_transactions())));
+    let mut sensor1 = Kxcj9::new_kxcj9_1018(CriticalSectionDevice::new(&bus), SlaveAddr::default());
+    let mut sensor2 = Kxcj9::new_kxcj9_1018(
+        CriticalSectionDevice::new(&bus),
+        SlaveAddr::Alternative(true),
+    );
+    sensor1.enable().unwrap();
+    sensor2.enable().unwrap();
+    bus.into_inner().into_inner().done();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn can_share_bus_with_mutex() {
+    use kxcj9::bus::MutexDevice;
+    use std::sync::Mutex;
+    let bus = Mutex::new(I2cMock::new(&shared_transactions()));
+    let mut sensor1 = Kxcj9::new_kxcj9_1018(MutexDevice::new(&bus), SlaveAddr::default());
+    let mut sensor2 = Kxcj9::new_kxcj9_1018(MutexDevice::new(&bus), SlaveAddr::Alternative(true));
+    sensor1.enable().unwrap();
+    sensor2.enable().unwrap();
+    bus.into_inner().unwrap().done();
+}
+
+#[test]
+fn detached_driver_keeps_configuration() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::RES]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::RES]),
+    ];
+    let sensor = Kxcj9::new_kxcj9_1018(I2cMock::new(&transactions), SlaveAddr::default());
+    let (mut sensor, mut i2c) = sensor.detach();
+    sensor.with_bus(&mut i2c, |s| s.enable()).unwrap();
+    sensor
+        .with_bus(&mut i2c, |s| s.set_resolution(kxcj9::Resolution::High))
+        .unwrap();
+    let mut sensor = sensor.attach(i2c);
+    sensor.disable().unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn detached_driver_can_read() {
+    let transactions = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Reg::XOUT_L],
+        vec![64, 0, 0, 0, 0, 0],
+    )];
+    let mut i2c = I2cMock::new(&transactions);
+    let (mut sensor, _) = Kxcj9::new_kxcj9_1018(I2cMock::new(&[]), SlaveAddr::default()).detach();
+    let measurement = sensor.with_bus(&mut i2c, |s| s.read_unscaled()).unwrap();
+    assert_eq!(64, measurement.x);
+    i2c.done();
+}
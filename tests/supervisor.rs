@@ -0,0 +1,131 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use hal::i2c::Transaction as I2cTrans;
+use kxcj9::{ConfigCheck, Supervisor, WakeUpInterruptConfig};
+
+mod common;
+use common::{destroy, new_1008, BitFlags as BF, Register as Reg, DEV_ADDR};
+
+fn read_config(ctrl1: u8, ctrl2: u8, data_ctrl: u8, int_ctrl1: u8, int_ctrl2: u8) -> [I2cTrans; 5] {
+    [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::CTRL1], vec![ctrl1]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::CTRL2], vec![ctrl2]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::DATA_CTRL], vec![data_ctrl]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_CTRL1], vec![int_ctrl1]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_CTRL2], vec![int_ctrl2]),
+    ]
+}
+
+fn read_default_config() -> [I2cTrans; 5] {
+    read_config(0, 0, 0x02, 0x10, 0x3F)
+}
+
+const ALL_MATCH: ConfigCheck = ConfigCheck {
+    ctrl1: true,
+    ctrl2: true,
+    data_ctrl: true,
+    int_ctrl1: true,
+    int_ctrl2: true,
+};
+
+#[test]
+fn default_config_matches() {
+    let mut sensor = new_1008(&read_default_config());
+    let check = sensor.verify_config().unwrap();
+    assert_eq!(ALL_MATCH, check);
+    assert!(check.matches());
+    destroy(sensor);
+}
+
+#[test]
+fn can_detect_mismatch() {
+    let mut transactions = vec![I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1])];
+    transactions.extend_from_slice(&read_default_config());
+    let mut sensor = new_1008(&transactions);
+    sensor.enable().unwrap();
+    let check = sensor.verify_config().unwrap();
+    assert_eq!(
+        ConfigCheck {
+            ctrl1: false,
+            ..ALL_MATCH
+        },
+        check
+    );
+    assert!(!check.matches());
+    destroy(sensor);
+}
+
+#[test]
+fn can_restore_config() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::DATA_CTRL, 0x02]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL1, 0x10]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL2, 0x3F]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_TIMER, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_THRESHOLD, 0x08]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+    ];
+    let mut sensor = new_1008(&transactions);
+    sensor.restore_config().unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn supervisor_does_nothing_if_config_matches() {
+    let mut sensor = new_1008(&read_default_config());
+    let mut supervisor = Supervisor::new(1000);
+    assert_eq!(Ok(None), supervisor.update(&mut sensor, 999));
+    assert_eq!(Ok(None), supervisor.update(&mut sensor, 1));
+    assert_eq!(0, supervisor.recovery_count());
+    destroy(sensor);
+}
+
+#[test]
+fn supervisor_restores_config_after_reset() {
+    let mut transactions = vec![
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL2, 0b0000_0011]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_TIMER, 1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_THRESHOLD, 8]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::WUFE]),
+    ];
+    // The device has been reset and reads the default values.
+    transactions.extend_from_slice(&read_default_config());
+    transactions.extend_from_slice(&[
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::DATA_CTRL, 0x02]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL1, 0x10]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL2, 0b0000_0011]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_TIMER, 1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_THRESHOLD, 8]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::WUFE]),
+    ]);
+    transactions.extend_from_slice(&read_config(BF::PC1 | BF::WUFE, 0, 0x02, 0x10, 0b0000_0011));
+    let mut sensor = new_1008(&transactions);
+    sensor.enable().unwrap();
+    let mut config = WakeUpInterruptConfig::default();
+    config.trigger_motion.x_negative = false;
+    config.trigger_motion.x_positive = false;
+    config.trigger_motion.y_negative = false;
+    config.trigger_motion.y_positive = false;
+    sensor.enable_wake_up_interrupt(config).unwrap();
+
+    let mut supervisor = Supervisor::new(100);
+    let check = supervisor.update(&mut sensor, 100).unwrap().unwrap();
+    assert_eq!(
+        ConfigCheck {
+            ctrl1: false,
+            int_ctrl2: false,
+            ..ALL_MATCH
+        },
+        check
+    );
+    assert_eq!(1, supervisor.recovery_count());
+    assert_eq!(Ok(None), supervisor.check(&mut sensor));
+    destroy(sensor);
+}
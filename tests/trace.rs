@@ -0,0 +1,89 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use hal::i2c::Transaction as I2cTrans;
+use hal::MockError;
+use kxcj9::{AccessKind, MemoryRecorder, RegisterAccess, TraceHook, WakeUpInterruptConfig};
+use std::io::ErrorKind;
+
+mod common;
+use common::{new_1008, BitFlags as BF, Register as Reg, DEV_ADDR};
+
+#[test]
+fn records_wake_up_interrupt_configuration() {
+    let transactions = [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL2, 0b0011_1111]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_TIMER, 1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_THRESHOLD, 8]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE]),
+    ];
+    let mut sensor = new_1008(&transactions).with_trace_hook(MemoryRecorder::<8>::new());
+    sensor
+        .enable_wake_up_interrupt(WakeUpInterruptConfig::default())
+        .unwrap();
+    let recorder = sensor.i2c().hook();
+    assert_eq!(6, recorder.len());
+    let expected = [
+        (Reg::CTRL1, 0),
+        (Reg::INT_CTRL2, 0b0011_1111),
+        (Reg::CTRL2, 0),
+        (Reg::WAKEUP_TIMER, 1),
+        (Reg::WAKEUP_THRESHOLD, 8),
+        (Reg::CTRL1, BF::WUFE),
+    ];
+    for (access, (register, value)) in recorder.iter().zip(expected.iter()) {
+        assert_eq!(AccessKind::Write, access.kind);
+        assert_eq!(DEV_ADDR, access.address);
+        assert_eq!(*register, access.register);
+        assert_eq!(&[*value], access.data());
+        assert!(access.success);
+    }
+    let (mut i2c, _) = sensor.destroy().destroy();
+    i2c.done();
+}
+
+#[test]
+fn records_reads_and_failures() {
+    let transactions = [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::WHO_AM_I], vec![0x0A]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1])
+            .with_error(MockError::Io(ErrorKind::Other)),
+    ];
+    let mut sensor = new_1008(&transactions).with_trace_hook(MemoryRecorder::<8>::new());
+    sensor.who_am_i().unwrap();
+    assert!(sensor.enable().is_err());
+    let recorder = sensor.i2c().hook();
+    let read = recorder.iter().next().unwrap();
+    assert_eq!(AccessKind::Read, read.kind);
+    assert_eq!(Reg::WHO_AM_I, read.register);
+    assert_eq!(&[0x0A], read.data());
+    assert!(read.success);
+    let write = recorder.iter().nth(1).unwrap();
+    assert_eq!(AccessKind::Write, write.kind);
+    assert!(!write.success);
+    let (mut i2c, _) = sensor.destroy().destroy();
+    i2c.done();
+}
+
+#[derive(Default)]
+struct CountingHook {
+    count: usize,
+}
+
+impl TraceHook for CountingHook {
+    fn trace(&mut self, _access: &RegisterAccess<'_>) {
+        self.count += 1;
+    }
+}
+
+#[test]
+fn can_use_borrowed_hook() {
+    let transactions = [I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1])];
+    let mut hook = CountingHook::default();
+    let mut sensor = new_1008(&transactions).with_trace_hook(&mut hook);
+    sensor.enable().unwrap();
+    let (mut i2c, _) = sensor.destroy().destroy();
+    i2c.done();
+    assert_eq!(1, hook.count);
+}
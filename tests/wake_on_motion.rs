@@ -0,0 +1,174 @@
+extern crate embedded_hal_mock as hal;
+extern crate kxcj9;
+use hal::i2c::Transaction as I2cTrans;
+use kxcj9::{OutputDataRate, PowerState, WakeOnMotion, WakeOnMotionConfig};
+
+mod common;
+use common::{destroy, new_1008, BitFlags as BF, Register as Reg, DEV_ADDR};
+
+const SLEEP_ODR: u8 = 0b000_1000;
+const ACTIVE_ODR: u8 = 0b000_0011;
+const FULL_POWER_ODR: u8 = 0b000_0110;
+
+fn new_wom() -> WakeOnMotion {
+    WakeOnMotion::new(WakeOnMotionConfig {
+        sleep_data_rate: OutputDataRate::Hz0_781,
+        active_data_rate: OutputDataRate::Hz100,
+        inactivity_timeout_ms: 100,
+        ..Default::default()
+    })
+}
+
+fn start_transactions() -> Vec<I2cTrans> {
+    vec![
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::DATA_CTRL, SLEEP_ODR]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::INT_CTRL2, 0b0011_1111]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL2, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_TIMER, 1]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::WAKEUP_THRESHOLD, 8]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_REL], vec![0]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::WUFE]),
+    ]
+}
+
+fn no_motion() -> I2cTrans {
+    I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![0, 0])
+}
+
+fn motion() -> [I2cTrans; 2] {
+    [
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_SOURCE1], vec![BF::WUFS, BF::ZPWU]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::INT_REL], vec![0]),
+    ]
+}
+
+fn set_odr(odr: u8) -> [I2cTrans; 3] {
+    [
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::DATA_CTRL, odr]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::PC1 | BF::WUFE]),
+    ]
+}
+
+#[test]
+fn can_start() {
+    let mut sensor = new_1008(&start_transactions());
+    let mut wom = new_wom();
+    wom.start(&mut sensor).unwrap();
+    assert_eq!(PowerState::Sleep, wom.state());
+    destroy(sensor);
+}
+
+#[test]
+fn stays_asleep_without_motion() {
+    let mut transactions = start_transactions();
+    transactions.push(no_motion());
+    transactions.push(no_motion());
+    let mut sensor = new_1008(&transactions);
+    let mut wom = new_wom();
+    wom.start(&mut sensor).unwrap();
+    assert_eq!(None, wom.update(&mut sensor, 60).unwrap());
+    assert_eq!(None, wom.update(&mut sensor, 60).unwrap());
+    assert_eq!(PowerState::Sleep, wom.state());
+    destroy(sensor);
+}
+
+#[test]
+fn wakes_up_on_motion_and_goes_back_to_sleep() {
+    let mut transactions = start_transactions();
+    transactions.extend_from_slice(&motion());
+    transactions.extend_from_slice(&set_odr(ACTIVE_ODR));
+    transactions.push(no_motion());
+    transactions.push(no_motion());
+    transactions.extend_from_slice(&set_odr(SLEEP_ODR));
+    let mut sensor = new_1008(&transactions);
+    let mut wom = new_wom();
+    wom.start(&mut sensor).unwrap();
+    assert_eq!(
+        Some(PowerState::Active),
+        wom.update(&mut sensor, 10).unwrap()
+    );
+    assert_eq!(None, wom.update(&mut sensor, 60).unwrap());
+    assert_eq!(60, wom.idle_ms());
+    assert_eq!(
+        Some(PowerState::Sleep),
+        wom.update(&mut sensor, 60).unwrap()
+    );
+    assert_eq!(PowerState::Sleep, wom.state());
+    destroy(sensor);
+}
+
+#[test]
+fn restores_resolution_when_going_back_to_sleep() {
+    let mut transactions = start_transactions();
+    transactions.extend_from_slice(&motion());
+    transactions.push(I2cTrans::write(DEV_ADDR, vec![Reg::CTRL1, BF::WUFE]));
+    transactions.push(I2cTrans::write(
+        DEV_ADDR,
+        vec![Reg::DATA_CTRL, FULL_POWER_ODR],
+    ));
+    transactions.push(I2cTrans::write(
+        DEV_ADDR,
+        vec![Reg::CTRL1, BF::PC1 | BF::WUFE | BF::RES],
+    ));
+    transactions.push(no_motion());
+    transactions.push(I2cTrans::write(
+        DEV_ADDR,
+        vec![Reg::CTRL1, BF::WUFE | BF::RES],
+    ));
+    transactions.push(I2cTrans::write(DEV_ADDR, vec![Reg::DATA_CTRL, SLEEP_ODR]));
+    transactions.push(I2cTrans::write(
+        DEV_ADDR,
+        vec![Reg::CTRL1, BF::PC1 | BF::WUFE | BF::RES],
+    ));
+    transactions.push(I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Reg::DATA_CTRL],
+        vec![SLEEP_ODR],
+    ));
+    transactions.push(I2cTrans::write(
+        DEV_ADDR,
+        vec![Reg::CTRL1, BF::WUFE | BF::RES],
+    ));
+    transactions.push(I2cTrans::write(
+        DEV_ADDR,
+        vec![Reg::CTRL1, BF::PC1 | BF::WUFE],
+    ));
+    let mut sensor = new_1008(&transactions);
+    let mut wom = WakeOnMotion::new(WakeOnMotionConfig {
+        sleep_data_rate: OutputDataRate::Hz0_781,
+        active_data_rate: OutputDataRate::Hz800,
+        inactivity_timeout_ms: 100,
+        ..Default::default()
+    });
+    wom.start(&mut sensor).unwrap();
+    wom.update(&mut sensor, 10).unwrap();
+    assert_eq!(
+        Some(PowerState::Sleep),
+        wom.update(&mut sensor, 100).unwrap()
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn motion_while_active_restarts_timeout() {
+    let mut transactions = start_transactions();
+    transactions.extend_from_slice(&motion());
+    transactions.extend_from_slice(&set_odr(ACTIVE_ODR));
+    transactions.push(no_motion());
+    transactions.extend_from_slice(&motion());
+    transactions.push(no_motion());
+    let mut sensor = new_1008(&transactions);
+    let mut wom = new_wom();
+    wom.start(&mut sensor).unwrap();
+    wom.update(&mut sensor, 10).unwrap();
+    assert_eq!(None, wom.update(&mut sensor, 90).unwrap());
+    assert_eq!(None, wom.update(&mut sensor, 90).unwrap());
+    assert_eq!(0, wom.idle_ms());
+    assert_eq!(None, wom.update(&mut sensor, 90).unwrap());
+    assert_eq!(PowerState::Active, wom.state());
+    destroy(sensor);
+}